@@ -24,7 +24,7 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_plugin(LogDiagnosticsPlugin::default())
-        .add_plugin(SkyboxPlugin)
+        .add_plugin(SkyboxPlugin::default())
         .add_startup_system(setup)
         .add_system(spin_camera)
         .run();
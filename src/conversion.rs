@@ -0,0 +1,422 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pixel-level routines used to convert images into skybox textures.
+//!
+//! Everything in this module works on plain byte buffers plus their dimensions, and doesn't touch
+//! Bevy's [`Image`](bevy::prelude::Image) or any render resources. The conversions done by
+//! [`SkyboxTextureConversion`](crate::SkyboxTextureConversion) are built on these, and they can be
+//! used directly in asset preprocessing tools and build scripts which don't run a Bevy app.
+//!
+//! ```
+//! use bevy_skybox_cubemap::conversion::{rotate_face, stack_faces, FaceRotation};
+//! // Six 2x2 faces with one byte per pixel.
+//! let face = [7u8; 4];
+//! let stacked = stack_faces([&face[..]; 6], 4).unwrap();
+//! assert_eq!(stacked.len(), 24);
+//!
+//! let rotated = rotate_face(&[1, 2, 3, 4], 2, 1, FaceRotation::Cw90);
+//! assert_eq!(rotated, [3, 1, 4, 2]);
+//! ```
+
+use std::fmt;
+
+/// Errors from the routines in this module. [`SkyboxError`](crate::SkyboxError) converts from
+/// this, so `?` works in code which returns either.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// One of the faces passed in had the wrong number of bytes.
+    FaceSizeMismatch {
+        /// Layer index of the face which had the wrong size.
+        face: usize,
+        /// Number of bytes expected for the face.
+        expected: usize,
+        /// Number of bytes actually in the face.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::FaceSizeMismatch {
+                face,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "face {} has {} bytes, but {} bytes were expected",
+                face, actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Clockwise rotation to apply to a single face of a skybox when converting it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaceRotation {
+    /// Leave the face as it is.
+    None,
+    /// Rotate the face 90 degrees clockwise.
+    Cw90,
+    /// Rotate the face 180 degrees.
+    Cw180,
+    /// Rotate the face 270 degrees clockwise (90 degrees counterclockwise).
+    Cw270,
+}
+
+impl Default for FaceRotation {
+    fn default() -> Self {
+        FaceRotation::None
+    }
+}
+
+/// Differences between the layout of a stacked skybox image and the layout described in the
+/// [crate] docs, which are fixed up while converting it. See
+/// [`SkyboxTextureConversion::make_array_with_strip_options`](crate::SkyboxTextureConversion::make_array_with_strip_options).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StripOptions {
+    /// The faces are stacked bottom-to-top, i.e. the last face (-Z) is at the top of the image.
+    pub reverse_order: bool,
+    /// Each face is flipped vertically, so its first row of pixels is at the bottom.
+    pub flip_each_face_y: bool,
+}
+
+/// Concatenates the bytes of six faces, given in layer order, into the data of a 6 layer array
+/// texture. Returns an error if any face isn't `face_len` bytes long.
+pub fn stack_faces(faces: [&[u8]; 6], face_len: usize) -> Result<Vec<u8>, ConversionError> {
+    for (face, data) in faces.iter().enumerate() {
+        if data.len() != face_len {
+            return Err(ConversionError::FaceSizeMismatch {
+                face,
+                expected: face_len,
+                actual: data.len(),
+            });
+        }
+    }
+    // An array texture's layers are stored one after another, which is exactly what concatenating
+    // the faces gives us.
+    Ok(faces.concat())
+}
+
+/// Rearranges the data of an image with 6 faces side by side in a single row into the data of the
+/// same faces stacked vertically, in the same order. The image is `rows` rows tall, and each row
+/// holds 6 stretches of `face_row_len` bytes, one per face. For block-compressed data, use rows of
+/// blocks rather than rows of pixels.
+///
+/// ```
+/// use bevy_skybox_cubemap::conversion::horizontal_to_stacked;
+/// // A 12x2 strip of 2x2 faces with one byte per pixel, where each pixel is its face's index.
+/// let strip = [[0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5]; 2].concat();
+/// let stacked = horizontal_to_stacked(&strip, 2, 2);
+/// assert_eq!(stacked, [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5]);
+/// ```
+pub fn horizontal_to_stacked(data: &[u8], face_row_len: usize, rows: usize) -> Vec<u8> {
+    let row_len = face_row_len * 6;
+    let mut stacked = Vec::with_capacity(data.len());
+    for face in 0..6 {
+        for row in data.chunks_exact(row_len).take(rows) {
+            stacked.extend_from_slice(&row[face * face_row_len..(face + 1) * face_row_len]);
+        }
+    }
+    stacked
+}
+
+/// Reorders and flips the faces of stacked image data according to `options`. Each face is
+/// `face_rows` rows of `row_len` bytes. The data may hold several frames of 6 faces; the order of
+/// the faces is reversed within each frame, so frames stay in order.
+pub fn rearrange_faces(
+    data: &[u8],
+    row_len: usize,
+    face_rows: usize,
+    options: StripOptions,
+) -> Vec<u8> {
+    let face_len = row_len * face_rows;
+    let mut rearranged = Vec::with_capacity(data.len());
+    for frame in data.chunks_exact(face_len * 6) {
+        let faces: Vec<&[u8]> = if options.reverse_order {
+            frame.chunks_exact(face_len).rev().collect()
+        } else {
+            frame.chunks_exact(face_len).collect()
+        };
+        for face in faces {
+            if options.flip_each_face_y {
+                for row in face.chunks_exact(row_len).rev() {
+                    rearranged.extend_from_slice(row);
+                }
+            } else {
+                rearranged.extend_from_slice(face);
+            }
+        }
+    }
+    rearranged
+}
+
+/// Rotates each of the square faces in `data`, which are `size` pixels on a side with
+/// `pixel_size` bytes per pixel, by the corresponding entry of `rotations`.
+pub fn rotate_faces(data: &mut [u8], size: usize, pixel_size: usize, rotations: [FaceRotation; 6]) {
+    let face_len = size * size * pixel_size;
+    for (face, rotation) in data.chunks_exact_mut(face_len).zip(rotations) {
+        if rotation != FaceRotation::None {
+            let rotated = rotate_face(face, size, pixel_size, rotation);
+            face.copy_from_slice(&rotated);
+        }
+    }
+}
+
+/// Rotates the pixels of a square face which is `size` pixels on a side.
+pub fn rotate_face(face: &[u8], size: usize, pixel_size: usize, rotation: FaceRotation) -> Vec<u8> {
+    let mut rotated = vec![0; face.len()];
+    let last = size - 1;
+    for y in 0..size {
+        for x in 0..size {
+            // Find the pixel in the source face which ends up at (x, y) after rotating.
+            let (src_x, src_y) = match rotation {
+                FaceRotation::None => (x, y),
+                FaceRotation::Cw90 => (y, last - x),
+                FaceRotation::Cw180 => (last - x, last - y),
+                FaceRotation::Cw270 => (last - y, x),
+            };
+            let src = (src_y * size + src_x) * pixel_size;
+            let dst = (y * size + x) * pixel_size;
+            rotated[dst..dst + pixel_size].copy_from_slice(&face[src..src + pixel_size]);
+        }
+    }
+    rotated
+}
+
+/// Copies a `width` by `height` pixel region with its top left corner at (`left`, `top`) out of an
+/// image which is `image_width` pixels wide with `pixel_size` bytes per pixel. The region must lie
+/// within the image.
+pub fn copy_region(
+    data: &[u8],
+    image_width: usize,
+    pixel_size: usize,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+) -> Vec<u8> {
+    let row_len = image_width * pixel_size;
+    let mut region = Vec::with_capacity(width * height * pixel_size);
+    for row in data.chunks_exact(row_len).skip(top).take(height) {
+        region.extend_from_slice(&row[left * pixel_size..(left + width) * pixel_size]);
+    }
+    region
+}
+
+/// Measures the uniform border of a square face which is `size` pixels on a side with
+/// `pixel_size` bytes per pixel: the number of rings of pixels around its edge, up to
+/// `max_thickness`, in which every byte of every pixel is within `tolerance` of the matching byte
+/// of `border`. At least one pixel in the middle is always left.
+///
+/// ```
+/// use bevy_skybox_cubemap::conversion::border_thickness;
+/// // A 4x4 face with a one pixel border of 0s around 2x2 pixels of 9s.
+/// let face = [0, 0, 0, 0, 0, 9, 9, 0, 0, 9, 9, 0, 0, 0, 0, 0];
+/// assert_eq!(border_thickness(&face, 4, 1, &[0], 0, 8), 1);
+/// assert_eq!(border_thickness(&face, 4, 1, &[9], 0, 8), 0);
+/// ```
+pub fn border_thickness(
+    face: &[u8],
+    size: usize,
+    pixel_size: usize,
+    border: &[u8],
+    tolerance: u8,
+    max_thickness: usize,
+) -> usize {
+    let is_border = |x: usize, y: usize| {
+        let start = (y * size + x) * pixel_size;
+        face[start..start + pixel_size]
+            .iter()
+            .zip(border)
+            .all(|(&a, &b)| (a as i16 - b as i16).abs() <= tolerance as i16)
+    };
+    let max_thickness = max_thickness.min(size.saturating_sub(1) / 2);
+    for ring in 0..max_thickness {
+        let last = size - 1 - ring;
+        let uniform = (ring..=last).all(|i| {
+            is_border(i, ring) && is_border(i, last) && is_border(ring, i) && is_border(last, i)
+        });
+        if !uniform {
+            return ring;
+        }
+    }
+    max_thickness
+}
+
+/// Expands pixels with `channels` channels of `channel_size` bytes each (1 or 2) to 8 bit RGBA.
+/// Single channel pixels are treated as grayscale and two channel pixels as grayscale with alpha;
+/// missing alpha is filled in as opaque. 16 bit channels, which are expected in native byte order,
+/// are reduced to 8 bits.
+pub fn expand_to_rgba8(data: &[u8], channels: usize, channel_size: usize) -> Vec<u8> {
+    let mut expanded = Vec::with_capacity(data.len() / (channels * channel_size) * 4);
+    for pixel in data.chunks_exact(channels * channel_size) {
+        // Keep the most significant byte of each channel.
+        let channel = |index: usize| {
+            let bytes = &pixel[index * channel_size..(index + 1) * channel_size];
+            if channel_size == 2 {
+                (u16::from_ne_bytes([bytes[0], bytes[1]]) >> 8) as u8
+            } else {
+                bytes[0]
+            }
+        };
+        let rgba = match channels {
+            1 => [channel(0), channel(0), channel(0), u8::MAX],
+            2 => [channel(0), channel(0), channel(0), channel(1)],
+            _ => [channel(0), channel(1), channel(2), channel(3)],
+        };
+        expanded.extend_from_slice(&rgba);
+    }
+    expanded
+}
+
+/// Decodes an sRGB-encoded color channel in `0.0..=1.0` to linear.
+///
+/// Colors have to be linear to be averaged or blended correctly; averaging sRGB values directly
+/// gives results which are too dark.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a linear color channel in `0.0..=1.0` as sRGB. The inverse of [`srgb_to_linear`].
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Reads the EXIF orientation tag from the bytes of a JPEG or PNG file. Returns `None` if the file
+/// isn't a JPEG or PNG, has no EXIF data, or its EXIF data has no orientation.
+///
+/// The orientation is a number from 1 to 8, where 1 means the pixels are stored upright. See
+/// [`orient_image`] for undoing the others.
+pub fn exif_orientation(file: &[u8]) -> Option<u16> {
+    const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if let Some(mut chunks) = file.strip_prefix(PNG_SIGNATURE) {
+        // PNG chunks are a length, a type, the data and a CRC.
+        while chunks.len() >= 12 {
+            let len = u32::from_be_bytes(chunks[..4].try_into().ok()?) as usize;
+            let data = chunks.get(8..8 + len)?;
+            match &chunks[4..8] {
+                b"eXIf" => return tiff_orientation(data),
+                b"IDAT" | b"IEND" => return None,
+                _ => chunks = chunks.get(12 + len..)?,
+            }
+        }
+    } else if let Some(mut segments) = file.strip_prefix(&[0xff, 0xd8]) {
+        // JPEG segments are a marker and a length which includes itself, then the data.
+        while segments.len() >= 4 && segments[0] == 0xff {
+            let marker = segments[1];
+            let len = u16::from_be_bytes([segments[2], segments[3]]) as usize;
+            let data = segments.get(4..2 + len)?;
+            match marker {
+                // APP1, which holds the EXIF data after a header.
+                0xe1 if data.starts_with(b"Exif\0\0") => return tiff_orientation(&data[6..]),
+                // The start of the image data, after which there is no more metadata.
+                0xda => return None,
+                _ => segments = &segments[2 + len..],
+            }
+        }
+    }
+    None
+}
+
+/// Reads the orientation tag from the first directory of EXIF data in TIFF format.
+fn tiff_orientation(tiff: &[u8]) -> Option<u16> {
+    const ORIENTATION_TAG: u16 = 0x0112;
+    let big_endian = match tiff.get(..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let u16_at = |offset: usize| {
+        let bytes = [*tiff.get(offset)?, *tiff.get(offset + 1)?];
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |offset: usize| {
+        let bytes: [u8; 4] = tiff.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    let directory = u32_at(4)? as usize;
+    let entries = u16_at(directory)?;
+    // Each entry is a tag, a type, a count and a 4 byte value.
+    (0..entries as usize)
+        .map(|entry| directory + 2 + entry * 12)
+        .find(|&entry| u16_at(entry) == Some(ORIENTATION_TAG))
+        .and_then(|entry| u16_at(entry + 8))
+}
+
+/// Undoes an EXIF `orientation` (see [`exif_orientation`]) of an image which is `width` by `height`
+/// pixels with `pixel_size` bytes per pixel, so that it's stored upright. Returns the upright
+/// pixels with their width and height, which are swapped for orientations which rotate the image
+/// by 90 degrees. Orientations outside of 2 to 8 leave the image as it is.
+///
+/// ```
+/// use bevy_skybox_cubemap::conversion::orient_image;
+/// // A 2x1 image stored upside-down, and one stored rotated 90 degrees counterclockwise.
+/// assert_eq!(orient_image(&[1, 2], 2, 1, 1, 3), (vec![2, 1], 2, 1));
+/// assert_eq!(orient_image(&[1, 2], 2, 1, 1, 6), (vec![1, 2], 1, 2));
+/// ```
+pub fn orient_image(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    pixel_size: usize,
+    orientation: u16,
+) -> (Vec<u8>, usize, usize) {
+    let (last_x, last_y) = (width.saturating_sub(1), height.saturating_sub(1));
+    let transposed = (5..=8).contains(&orientation);
+    let (out_width, out_height) = if transposed {
+        (height, width)
+    } else {
+        (width, height)
+    };
+    let mut oriented = vec![0; data.len()];
+    for y in 0..out_height {
+        for x in 0..out_width {
+            // Find the stored pixel which is shown at (x, y).
+            let (src_x, src_y) = match orientation {
+                2 => (last_x - x, y),
+                3 => (last_x - x, last_y - y),
+                4 => (x, last_y - y),
+                5 => (y, x),
+                6 => (y, last_y - x),
+                7 => (last_x - y, last_y - x),
+                8 => (last_x - y, x),
+                _ => (x, y),
+            };
+            let src = (src_y * width + src_x) * pixel_size;
+            let dst = (y * out_width + x) * pixel_size;
+            oriented[dst..dst + pixel_size].copy_from_slice(&data[src..src + pixel_size]);
+        }
+    }
+    (oriented, out_width, out_height)
+}
@@ -29,7 +29,7 @@
 //! // Install the skybox plugin:
 //! App::build()
 //!     .add_plugins(DefaultPlugins)
-//!     .add_plugin(SkyboxPlugin)
+//!     .add_plugin(SkyboxPlugin::default())
 //!     .add_startup_system(setup.system());
 //!
 //! // Configure the skybox.
@@ -66,6 +66,14 @@
 //! In case you want your skybox to have a different orientation, the rotation compoenent of the skybox's
 //! transform *is* respected.
 //!
+//! Stereo rendering for VR works by rendering each eye with its own camera. The skybox is
+//! drawn separately for every view, centered on that view's camera and using its projection, so
+//! it lines up in both eyes without any extra setup. Drawing both eyes in a single pass to a
+//! multi-layer render target (multiview) isn't supported yet, since this version of Bevy's render
+//! pipelines have no way to enable multiview or give shaders the view index. Once they do, the
+//! skybox shaders only need to pick the view's matrices by that index, as the sky itself doesn't
+//! depend on anything else about the view.
+//!
 //! # Texture Layout
 //!
 //! In order to use a Skybox, you need a properly formatted Skybox texture. Appropriate textures for
@@ -83,7 +91,8 @@
 //! | Left (-X) | Front (-Z)  | Right (+X) | Back (+Z) |
 //! |           | Bottom (-Y) |            |           |
 //!
-//! For rendering, the faces are used as separate layers of an array texture in this order:
+//! For rendering, the faces are used as separate layers of an array texture in this order (also
+//! available in code as [`SKYBOX_FACE_ORDER`]):
 //!
 //! * Right (+X)
 //! * Left (-X)
@@ -97,6 +106,20 @@
 //! the image, send it to [`SkyboxTextureConversion`], which will use
 //! [`Texture::reinterpret_stacked_2d_as_array`] to convert it to a 6 layer array once it is loaded.
 //!
+//! If your texture is stored in a format that supports array layers or cubemaps (such as KTX2 or
+//! DDS) and already has the 6 faces as layers in the order above, no conversion is needed. Use
+//! [`SkyboxMaterial::from_cubemap_texture`] and skip [`SkyboxTextureConversion`] entirely.
+//!
+//! This is also the recommended way to use block-compressed textures (BCn, ASTC, or ETC2), which
+//! save a lot of memory for large skyboxes. Compressed formats are sampled just like uncompressed
+//! ones, so they need no special setup beyond enabling the matching [`WgpuFeatures`] (e.g.
+//! `TEXTURE_COMPRESSION_BC`) if the device doesn't have them by default. Stacked compressed images
+//! can still be converted with [`SkyboxTextureConversion::make_array`] as long as each face is a
+//! whole number of compression blocks, but conversions which move pixels around, such as
+//! [`SkyboxTextureConversion::make_array_with_orientations`], don't support them.
+//!
+//! [`WgpuFeatures`]: bevy::render::render_resource::WgpuFeatures
+//!
 //! Here is the above net rearranged into the correct order for a skybox texture:
 //!
 //! <img src="https://raw.githubusercontent.com/google/bevy_skybox_cubemap/main/docimgs/array_format.png" />
@@ -112,7 +135,8 @@
 //!
 //! <img src="https://raw.githubusercontent.com/google/bevy_skybox_cubemap/main/docimgs/shifted_net.png" />
 //!
-//! You would then rearrange from this net to the same vertical layout as above.
+//! You would then rearrange from this net to the same vertical layout as above. [`net_to_array`]
+//! and [`NetLayout`] can do this rearranging for you, for either kind of net.
 //!
 //! Alternately, if want a specific face to be used as the "front" and that face isn't the one that
 //! matches the orientation of the top and bottom, you could instead rotate the top and bottom when
@@ -147,24 +171,230 @@
 //! This is not an officially supported Google product.
 
 use bevy::{
-    ecs::system::{lifetimeless::SRes, SystemParamItem},
-    pbr::{MaterialPipeline, NotShadowCaster, NotShadowReceiver, SpecializedMaterial},
+    asset::{HandleId, LoadState},
+    core_pipeline::{self, draw_3d_graph, Opaque3d, Transparent3d},
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    ecs::{
+        schedule::ShouldRun,
+        system::{
+            lifetimeless::{Read, SQuery, SRes, SResMut},
+            SystemParamItem,
+        },
+    },
+    pbr::{
+        AlphaMode, AmbientLight, DrawMesh, MaterialPipeline, MeshPipeline, MeshPipelineKey,
+        NotShadowCaster, NotShadowReceiver, SetMaterialBindGroup, SetMeshBindGroup,
+        SetMeshViewBindGroup, SpecializedMaterial,
+    },
     prelude::*,
     reflect::TypeUuid,
     render::{
-        render_asset::{PrepareAssetError, RenderAsset, RenderAssets},
+        camera::{ActiveCameras, CameraPlugin, PerspectiveProjection},
+        mesh::PrimitiveTopology,
+        primitives::Aabb,
+        render_asset::{PrepareAssetError, RenderAsset, RenderAssetPlugin, RenderAssets},
+        render_component::ExtractComponentPlugin,
+        render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext, SlotInfo, SlotType},
+        render_phase::{
+            sort_phase_system, AddRenderCommand, CachedPipelinePhaseItem, DrawFunctionId,
+            DrawFunctions, EntityPhaseItem, EntityRenderCommand, PhaseItem, RenderCommandResult,
+            RenderPhase, SetItemPipeline, TrackedRenderPass,
+        },
         render_resource::{
             std140::{AsStd140, Std140},
             BindGroup, BindGroupDescriptor, BindGroupEntry, Buffer, BufferInitDescriptor,
             BufferUsages, Face, RenderPipelineDescriptor, ShaderStage, ShaderStages, *,
         },
-        renderer::RenderDevice,
-        view::visibility::NoFrustumCulling,
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        texture::{BevyDefault, CachedTexture, GpuImage, TextureCache},
+        view::{
+            visibility::{Layer, NoFrustumCulling, VisibilitySystems},
+            ExtractedView, RenderLayers, VisibleEntities,
+        },
+        RenderApp, RenderStage,
     },
+    tasks::{AsyncComputeTaskPool, Task},
+    transform::TransformSystem,
+    utils::{HashMap, HashSet},
 };
+use futures_lite::future;
+use std::sync::{Arc, RwLock};
+
+pub mod conversion;
+
+pub use conversion::{FaceRotation, StripOptions};
+
+/// Configures the skybox render pipeline and support for [`SkyboxMaterial`]. Also sets up the system
+/// for converting textures queued in [`SkyboxTextureConversion`], unless that is turned off.
+#[derive(Debug, Clone)]
+pub struct SkyboxPlugin {
+    /// Whether to add the [`SkyboxTextureConversion`] resource and the system which converts the
+    /// textures queued in it. Defaults to `true`. If you only use textures which are already array
+    /// textures, you can turn this off to leave them out entirely.
+    pub auto_convert: bool,
+    /// Whether to automatically spawn a color-only skybox using the app's [`ClearColor`] whenever
+    /// there is no other skybox. Defaults to `false`.
+    ///
+    /// This works around Bevy antialiasing objects against white rather than the clear color (see
+    /// [`SkyboxMaterial`]), without having to set up a skybox yourself. The automatic skybox
+    /// follows changes to the `ClearColor` resource, is marked with [`ClearColorSkybox`], and is
+    /// despawned as soon as any other skybox exists. There is never more than one of them; extra
+    /// ones, e.g. from loading a saved scene, are despawned.
+    pub auto_clear_color_fix: bool,
+    /// Whether skyboxes whose texture failed to load, or doesn't exist, draw a subtle checkerboard
+    /// instead of their color. Defaults to `false`.
+    ///
+    /// This is meant for development: it makes a missing texture obvious without filling the
+    /// screen with a solid color. Materials which intentionally have no texture, like the
+    /// [`ClearColorSkybox`], are drawn as usual, and so are textures which are still loading or
+    /// being converted.
+    pub debug_missing_pattern: bool,
+    /// The depth convention of the render pipeline skyboxes are drawn with, which decides the
+    /// depth skyboxes are drawn at so that they end up at the far plane. Defaults to
+    /// [`SkyboxDepthConvention::ReverseZ`], which is what Bevy uses; only change it if your
+    /// renderer has been set up with a standard depth buffer.
+    pub depth_convention: SkyboxDepthConvention,
+    /// Whether skyboxes are queued into Bevy's standard 3d phases, like any other material.
+    /// Defaults to `true`.
+    ///
+    /// Turn this off to draw skyboxes in a phase of your own, e.g. in a render graph with custom
+    /// passes. The plugin then sets up everything except queueing: the `Assets<SkyboxMaterial>`,
+    /// extracting and preparing materials, and the `MaterialPipeline<SkyboxMaterial>` and
+    /// `SpecializedPipelines<MaterialPipeline<SkyboxMaterial>>` resources in the render world. Your
+    /// queue system specializes the pipeline with the material's key, like Bevy's
+    /// `queue_material_meshes` does, and adds items drawn with `DrawMaterial<SkyboxMaterial>` to
+    /// your phase, which decides where the skybox ends up relative to your other passes.
+    pub queue_in_default_phases: bool,
+    /// Whether the plugin registers `SkyboxMaterial` with the renderer by adding
+    /// `MaterialPlugin::<SkyboxMaterial>` (or the parts of it described under
+    /// [`queue_in_default_phases`](Self::queue_in_default_phases)). Defaults to `true`.
+    ///
+    /// Turn this off if you add `MaterialPlugin::<SkyboxMaterial>`, or your own plugin which
+    /// registers the material, yourself, since registering it twice conflicts. The skybox plugin
+    /// then only adds the shaders, mesh, textures and systems, and skyboxes aren't drawn until
+    /// the material is registered some other way. `queue_in_default_phases` has no effect.
+    pub add_material_plugin: bool,
+    /// Whether skyboxes are rendered at half the resolution of the view and then stretched over
+    /// it. Defaults to `false`.
+    ///
+    /// The skybox shader then only runs for a quarter of the pixels, which helps on devices which
+    /// are limited by fill rate, while smooth skyboxes look nearly the same. Sharp details like
+    /// stars get blurrier. All skyboxes visible in the 3d camera's view are rendered into a half
+    /// resolution texture in a render graph node before the main pass, opaque ones first and then
+    /// blended ones in the order of their entities, and a single full screen draw in the opaque
+    /// phase stretches it into the view behind the scene. [`SkyboxLayer`] has no effect, and
+    /// skyboxes are queued into this pass instead of the default phases, whatever
+    /// [`queue_in_default_phases`](Self::queue_in_default_phases) is set to. Bevy's default
+    /// plugins must be added before the skybox plugin for this, since it adds to their render
+    /// graph.
+    pub half_resolution: bool,
+    /// Whether skyboxes are drawn as a single triangle covering the view, instead of with their
+    /// mesh. Defaults to `false`.
+    ///
+    /// The view direction of each pixel is worked out from the camera in the vertex shader, so the
+    /// skybox needs neither a mesh nor a transform: skybox entities only need a
+    /// `Handle<SkyboxMaterial>` and visibility components, and any mesh they have is ignored. The
+    /// skybox entity's rotation has no effect either; use [`SkyboxMaterial::rotation`] instead.
+    /// Blended skyboxes are drawn behind all other transparent objects, in the order of their
+    /// [`SkyboxLayer`]s. Skyboxes are queued by the plugin instead of in the default phases,
+    /// whatever [`queue_in_default_phases`](Self::queue_in_default_phases) is set to. This is
+    /// ignored if [`half_resolution`](Self::half_resolution) is set.
+    pub fullscreen_triangle: bool,
+}
+
+/// Depth buffer conventions the skybox can be drawn with. See [`SkyboxPlugin::depth_convention`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxDepthConvention {
+    /// The far plane is at depth 0 and the depth buffer is cleared to 0, with closer fragments
+    /// having greater depth. This is what Bevy uses.
+    ReverseZ,
+    /// The far plane is at depth 1 and the depth buffer is cleared to 1, with closer fragments
+    /// having smaller depth.
+    Standard,
+}
+
+impl Default for SkyboxDepthConvention {
+    fn default() -> Self {
+        SkyboxDepthConvention::ReverseZ
+    }
+}
+
+impl SkyboxDepthConvention {
+    /// Depth skyboxes are drawn at with this convention, just in front of the far plane. This is
+    /// the `SKYBOX_DEPTH` of the skybox vertex shaders.
+    pub fn skybox_depth(self) -> f32 {
+        match self {
+            SkyboxDepthConvention::ReverseZ => 1.0e-7,
+            SkyboxDepthConvention::Standard => 1.0 - 1.0e-6,
+        }
+    }
+
+    /// Depth comparison skyboxes are drawn with, which lets them through wherever nothing nearer
+    /// than [`skybox_depth`](Self::skybox_depth) has been drawn. Skyboxes don't write depth, so
+    /// scene geometry drawn before or after them still covers them:
+    ///
+    /// ```
+    /// # use bevy::render::render_resource::CompareFunction;
+    /// # use bevy_skybox_cubemap::SkyboxDepthConvention;
+    /// // Whether a fragment at `depth` passes the depth test against `stored`.
+    /// fn passes(compare: CompareFunction, depth: f32, stored: f32) -> bool {
+    ///     match compare {
+    ///         CompareFunction::GreaterEqual => depth >= stored,
+    ///         CompareFunction::LessEqual => depth <= stored,
+    ///         _ => unreachable!(),
+    ///     }
+    /// }
+    ///
+    /// for (convention, far, near) in [
+    ///     (SkyboxDepthConvention::ReverseZ, 0.0, 1.0),
+    ///     (SkyboxDepthConvention::Standard, 1.0, 0.0),
+    /// ] {
+    ///     let compare = convention.depth_compare();
+    ///     let sky = convention.skybox_depth();
+    ///     // Where nothing has been drawn, the depth buffer still holds the far plane.
+    ///     assert!(passes(compare, sky, far));
+    ///     // Geometry anywhere from the near plane to right in front of the sky occludes it.
+    ///     for t in [0.0, 0.5, 0.99999] {
+    ///         let scene = near + (sky - near) * t;
+    ///         assert!(passes(compare, scene, far));
+    ///         assert!(!passes(compare, sky, scene));
+    ///     }
+    /// }
+    /// ```
+    pub fn depth_compare(self) -> CompareFunction {
+        match self {
+            SkyboxDepthConvention::ReverseZ => CompareFunction::GreaterEqual,
+            SkyboxDepthConvention::Standard => CompareFunction::LessEqual,
+        }
+    }
+}
+
+impl SkyboxPlugin {
+    /// Creates a plugin which doesn't set up [`SkyboxTextureConversion`], for apps which only use
+    /// textures which are already in the layout needed for skyboxes or which convert them some
+    /// other way.
+    pub fn without_conversion() -> Self {
+        Self {
+            auto_convert: false,
+            ..Default::default()
+        }
+    }
+}
 
-/// Configures the skybox render pipeline and support for [`SkyboxMaterial`]. Also sets up the system for [`
-pub struct SkyboxPlugin;
+impl Default for SkyboxPlugin {
+    fn default() -> Self {
+        Self {
+            auto_convert: true,
+            auto_clear_color_fix: false,
+            debug_missing_pattern: false,
+            depth_convention: SkyboxDepthConvention::default(),
+            queue_in_default_phases: true,
+            add_material_plugin: true,
+            half_resolution: false,
+            fullscreen_triangle: false,
+        }
+    }
+}
 
 impl Plugin for SkyboxPlugin {
     fn build(&self, app: &mut App) {
@@ -178,343 +408,8399 @@ impl Plugin for SkyboxPlugin {
             SKYBOX_FRAGMENT_SHADER_HANDLE,
             Shader::from_glsl(include_str!("skybox.frag"), ShaderStage::Fragment),
         );
+        shaders.set_untracked(
+            SKYBOX_DEBUG_FRAGMENT_SHADER_HANDLE,
+            Shader::from_glsl(include_str!("skybox_debug.frag"), ShaderStage::Fragment),
+        );
+        shaders.set_untracked(
+            SKYBOX_FULLSCREEN_VERTEX_SHADER_HANDLE,
+            Shader::from_glsl(include_str!("skybox_fullscreen.vert"), ShaderStage::Vertex),
+        );
+        shaders.set_untracked(
+            SKYBOX_UPSAMPLE_FRAGMENT_SHADER_HANDLE,
+            Shader::from_glsl(include_str!("skybox_upsample.frag"), ShaderStage::Fragment),
+        );
 
         // Add the Skybox mesh
         let mut meshes = app.world.get_resource_mut::<Assets<Mesh>>().unwrap();
-        // Skybox mesh needs to be large enough not to get caught in the camera's near-clip plane (but
-        // can otherwise be any value).
+        // The vertex shader places the skybox at a fixed depth regardless of the mesh size, so the
+        // size of the cube doesn't matter.
         meshes.set_untracked(SKYBOX_MESH_HANDLE, Mesh::from(shape::Cube { size: 1.0 }));
 
-        app.add_plugin(MaterialPlugin::<SkyboxMaterial>::default())
-            .add_system(convert_skyboxes)
-            .init_resource::<SkyboxTextureConversion>();
+        // Add the transparent texture used in place of a missing overlay.
+        let mut images = app.world.get_resource_mut::<Assets<Image>>().unwrap();
+        images.set_untracked(
+            SKYBOX_EMPTY_TEXTURE_HANDLE,
+            Image::new_fill(
+                Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 6,
+                },
+                TextureDimension::D2,
+                &[0, 0, 0, 0],
+                TextureFormat::Rgba8UnormSrgb,
+            ),
+        );
+        // Add the white texture bound in place of a missing skybox texture.
+        images.set_untracked(
+            SKYBOX_WHITE_TEXTURE_HANDLE,
+            Image::new_fill(
+                Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 6,
+                },
+                TextureDimension::D2,
+                &[255, 255, 255, 255],
+                TextureFormat::Rgba8UnormSrgb,
+            ),
+        );
+        // Add the 3d texture bound in place of a missing color grading LUT. The shader skips the
+        // LUT for those materials, so its contents don't matter.
+        images.set_untracked(
+            SKYBOX_EMPTY_LUT_HANDLE,
+            Image::new_fill(
+                Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D3,
+                &[255, 255, 255, 255],
+                TextureFormat::Rgba8Unorm,
+            ),
+        );
+        // Add the texture bound in place of a missing mask.
+        images.set_untracked(
+            SKYBOX_EMPTY_MASK_HANDLE,
+            Image::new_fill(
+                Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[255, 255, 255, 255],
+                TextureFormat::Rgba8Unorm,
+            ),
+        );
+
+        let default_phases =
+            self.queue_in_default_phases && !self.half_resolution && !self.fullscreen_triangle;
+        if self.add_material_plugin && default_phases {
+            app.add_plugin(MaterialPlugin::<SkyboxMaterial>::default());
+        } else if self.add_material_plugin {
+            // Everything `MaterialPlugin` sets up, except for queueing into the default phases.
+            app.add_asset::<SkyboxMaterial>()
+                .add_plugin(ExtractComponentPlugin::<Handle<SkyboxMaterial>>::default())
+                .add_plugin(RenderAssetPlugin::<SkyboxMaterial>::default());
+            if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+                render_app
+                    .init_resource::<MaterialPipeline<SkyboxMaterial>>()
+                    .init_resource::<SpecializedPipelines<MaterialPipeline<SkyboxMaterial>>>();
+            }
+        }
+
+        app.add_system(refresh_skybox_materials)
+            .add_system(animate_skybox_lod)
+            .add_system(apply_skybox_color_spaces)
+            .add_system(animate_skybox_tints)
+            .add_system(update_skybox_info)
+            .add_system(update_skybox_ambient_light)
+            .add_system(draw_skybox_debug_edges)
+            .add_system(insert_skybox_markers)
+            .add_system(update_skybox_status.after(SkyboxSystem::ConvertTextures))
+            .add_startup_system(setup_skybox_diagnostics)
+            .add_system(measure_skybox_vram)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                hide_skyboxes.before(VisibilitySystems::CheckVisibility),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                align_skyboxes_to_lights.after(TransformSystem::TransformPropagate),
+            )
+            .register_type::<SkyboxMaterial>()
+            .init_resource::<SkyboxDebugFaces>()
+            .init_resource::<SkyboxDebugEdges>()
+            .init_resource::<SkyboxQuality>()
+            .init_resource::<SkyboxLodBlend>()
+            .init_resource::<SkyboxFades>()
+            .init_resource::<SkyboxEnvironment>()
+            .add_system(fade_in_skyboxes)
+            .add_system(transition_skyboxes)
+            .add_system(animate_skybox_motion.label(SkyboxSystem::Rotate))
+            .add_system(animate_skybox_rotations.label(SkyboxSystem::Rotate))
+            .add_system(rotate_skyboxes_to_targets.label(SkyboxSystem::Rotate))
+            .add_system(sync_skybox_rotation_groups.after(SkyboxSystem::Rotate))
+            .add_system(allow_skybox_cube_copies);
+
+        if self.auto_clear_color_fix {
+            app.add_system(manage_clear_color_skybox);
+        }
+
+        if self.debug_missing_pattern {
+            app.init_resource::<SkyboxMissingTextures>()
+                .add_system(find_missing_skybox_textures.after(SkyboxSystem::ConvertTextures));
+        }
+
+        if self.auto_convert {
+            app.add_system(convert_skyboxes.label(SkyboxSystem::ConvertTextures))
+                .init_resource::<SkyboxTextureConversion>()
+                .add_event::<SkyboxConversionEvent>();
+        }
+
+        #[cfg(debug_assertions)]
+        app.add_system(warn_parented_skyboxes);
+
+        let readiness = SkyboxReadiness::default();
+        let gpu_conversion = SkyboxGpuConversion::default();
+        app.insert_resource(readiness.clone())
+            .insert_resource(gpu_conversion.clone())
+            .add_system(release_skybox_gpu_targets);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<SkyboxBindGroupLayouts>()
+                .init_resource::<SkyboxCubeTextures>()
+                .init_resource::<SkyboxWarnings>()
+                .add_system_to_stage(RenderStage::Queue, free_skybox_cube_textures)
+                .insert_resource(readiness)
+                .insert_resource(gpu_conversion)
+                .add_system_to_stage(RenderStage::Queue, update_skybox_gpu_targets)
+                .insert_resource(self.depth_convention)
+                .add_system_to_stage(RenderStage::Extract, extract_skybox_settings)
+                .add_system_to_stage(RenderStage::Extract, extract_skybox_texture_descriptors)
+                .add_system_to_stage(RenderStage::Extract, extract_skybox_layers)
+                .add_system_to_stage(RenderStage::Queue, update_skybox_uniforms)
+                .add_system_to_stage(
+                    RenderStage::PhaseSort,
+                    order_skybox_layers.exclusive_system().at_start(),
+                )
+                .add_system_to_stage(RenderStage::Queue, update_skybox_readiness);
+            let mut graph = render_app.world.get_resource_mut::<RenderGraph>().unwrap();
+            graph.add_node(SKYBOX_GPU_CONVERSION_NODE, SkyboxGpuConversionNode);
+            graph
+                .add_node_edge(
+                    SKYBOX_GPU_CONVERSION_NODE,
+                    core_pipeline::node::MAIN_PASS_DEPENDENCIES,
+                )
+                .unwrap();
+            if self.half_resolution {
+                add_skybox_half_res_pass(render_app);
+            } else if self.fullscreen_triangle {
+                render_app
+                    .init_resource::<SkyboxFullscreenPipeline>()
+                    .init_resource::<SpecializedPipelines<SkyboxFullscreenPipeline>>()
+                    .add_render_command::<Opaque3d, DrawSkyboxFullscreen>()
+                    .add_render_command::<Transparent3d, DrawSkyboxFullscreen>()
+                    .add_system_to_stage(RenderStage::Queue, queue_skybox_fullscreen);
+            }
+        }
     }
 }
 
-/// Bundle for spawning Skybox entities. Note that you should be able to use defaults for everything
-/// besides `material`. The only other field you may want to touch is `transform` which can be used
-/// to rotate the skybox if desired. Translations applied to skyboxes are ignored.
+/// Removes the assets the [`SkyboxPlugin`] registers (the skybox shaders, mesh, and fallback
+/// textures) and clears the [`SkyboxTextureConversion`] queue, cancelling any running conversions.
 ///
-/// When inserting a skybox bundle, you should generally use `..Default::default()` for every
-/// property except the `material` and occasionally `transform` (if you want to rotate the skybox
-/// from its default orientation).
+/// Bevy can't remove plugins, so this is the teardown path for apps which want to get rid of
+/// everything the skybox plugin set up, e.g. before reusing a world or when running several apps
+/// in one process. Call it once no skyboxes are left, since they can't be drawn without these
+/// assets. It can be run as an exclusive system or called directly with the app's world.
+pub fn cleanup_skybox(world: &mut World) {
+    if let Some(mut shaders) = world.get_resource_mut::<Assets<Shader>>() {
+        for handle in [
+            SKYBOX_VERTEX_SHADER_HANDLE,
+            SKYBOX_FRAGMENT_SHADER_HANDLE,
+            SKYBOX_DEBUG_FRAGMENT_SHADER_HANDLE,
+            SKYBOX_FULLSCREEN_VERTEX_SHADER_HANDLE,
+            SKYBOX_UPSAMPLE_FRAGMENT_SHADER_HANDLE,
+        ] {
+            shaders.remove(handle);
+        }
+    }
+    if let Some(mut meshes) = world.get_resource_mut::<Assets<Mesh>>() {
+        meshes.remove(SKYBOX_MESH_HANDLE);
+    }
+    if let Some(mut images) = world.get_resource_mut::<Assets<Image>>() {
+        images.remove(SKYBOX_EMPTY_TEXTURE_HANDLE);
+        images.remove(SKYBOX_WHITE_TEXTURE_HANDLE);
+        images.remove(SKYBOX_EMPTY_LUT_HANDLE);
+        images.remove(SKYBOX_EMPTY_MASK_HANDLE);
+    }
+    if let Some(mut conversions) = world.get_resource_mut::<SkyboxTextureConversion>() {
+        conversions.clear();
+    }
+}
+
+/// Labels for the systems added by [`SkyboxPlugin`], for ordering your own systems relative to them.
+///
+/// For example, a system which should only run once a texture queued with
+/// [`SkyboxTextureConversion::make_array`] has been converted can be ordered after the conversion
+/// and gated on [`skybox_texture_ready`]:
 ///
 /// ```no_run
 /// # use bevy::prelude::*;
-/// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxPlugin, SkyboxTextureConversion};
-/// # App::new()
-/// #     .add_startup_system(setup.system());
-/// # fn setup(
-/// #     mut commands: Commands,
-/// #     asset_server: Res<AssetServer>,
-/// #     mut meshes: ResMut<Assets<Mesh>>,
-/// #     mut materials: ResMut<Assets<StandardMaterial>>,
-/// #     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
-/// #     mut skybox_conversion: ResMut<SkyboxTextureConversion>,
-/// # ) {
-/// # let skybox_texture = asset_server.load("labeled_skybox.png");
-/// # skybox_conversion.make_array(skybox_texture.clone());
-/// commands.spawn_bundle(SkyboxBundle::new(
-///     skyboxes.add(SkyboxMaterial::from_texture(skybox_texture)),
-/// ));
-/// # }
+/// # use bevy_skybox_cubemap::{skybox_texture_ready, SkyboxSystem};
+/// # fn spawn_sky() {}
+/// # let sky_texture: Handle<Image> = Default::default();
+/// # App::new().add_system(
+/// spawn_sky
+///     .after(SkyboxSystem::ConvertTextures)
+///     .with_run_criteria(skybox_texture_ready(sky_texture)),
+/// # );
 /// ```
-#[derive(Bundle)]
-pub struct SkyboxBundle {
-    /// Material to use for the skybox. Defaults to a garish pink. In most usage this should be the
-    /// only field you need to set.
-    pub material: Handle<SkyboxMaterial>,
-    /// Mesh to use for the skybox. Defaults to [`SKYBOX_MESH_HANDLE`], which is a unit cube. You
-    /// shouldn't ever need to use any other mesh. Because of how cubemap sampling works, probably
-    /// any mesh that completely surrounds the camera would work equally well, but only the unit
-    /// cube is officially supported by this crate.
-    pub mesh: Handle<Mesh>,
-    /// User indication of whether the skybox is visible
-    pub visibility: Visibility,
-    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
-    pub computed_visibility: ComputedVisibility,
-    pub no_frustum_culling: NoFrustumCulling,
-    pub not_shadow_caster: NotShadowCaster,
-    pub not_shadow_receiver: NotShadowReceiver,
-    /// Transform can be used to manipulate the rotation of the skybox.
-    pub transform: Transform,
-    /// Transforms get computed into global transforms used for drawing based on parenting. Note
-    /// that it doesn't make much sense to add a skybox as a child of any other entity; it should
-    /// usually be freestanding.
-    pub global_transform: GlobalTransform,
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemLabel)]
+pub enum SkyboxSystem {
+    /// The system which converts textures queued in [`SkyboxTextureConversion`]. Textures are
+    /// converted in place during this system, so systems ordered after it in the same frame see
+    /// the converted texture.
+    ConvertTextures,
+    /// The systems which rotate skyboxes for [`SkyboxMotion`], [`SkyboxRotationAnimation`] and
+    /// [`SkyboxRotateTo`]. [`SkyboxRotationGroup`]s are synchronized after them; label your own
+    /// systems which rotate group leaders with it too, so the groups follow in the same frame.
+    Rotate,
 }
 
-impl SkyboxBundle {
-    /// Convenience constructor for [`SkyboxBundle`]. Sets the material and uses defaults for
-    /// everything else. In most use cases you should only need to set the material.
-    pub fn new(material: Handle<SkyboxMaterial>) -> Self {
-        Self {
-            material,
-            ..Default::default()
+/// Creates a run criteria which runs a system once the given texture has loaded and is no longer
+/// waiting for conversion by [`SkyboxTextureConversion`]. Use it with `with_run_criteria`, together
+/// with ordering after [`SkyboxSystem::ConvertTextures`]; see [`SkyboxSystem`].
+///
+/// The criteria keeps returning yes every frame once the texture is ready, so for one-shot work
+/// also record that it has been done, e.g. in a `Local`.
+pub fn skybox_texture_ready(
+    texture: Handle<Image>,
+) -> impl FnMut(Res<Assets<Image>>, Option<Res<SkyboxTextureConversion>>) -> ShouldRun {
+    move |images, conversions| {
+        let converting = conversions.map_or(false, |conversions| conversions.is_queued(&texture));
+        if images.contains(&texture) && !converting {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
         }
     }
 }
 
-impl Default for SkyboxBundle {
-    fn default() -> Self {
-        Self {
-            material: Default::default(),
-            mesh: SKYBOX_MESH_HANDLE.typed(),
-            visibility: Default::default(),
-            computed_visibility: Default::default(),
-            no_frustum_culling: NoFrustumCulling,
-            not_shadow_caster: NotShadowCaster,
-            not_shadow_receiver: NotShadowReceiver,
-            transform: Default::default(),
-            global_transform: Default::default(),
+/// Run criteria which runs a system only while at least one skybox exists, i.e. an entity with a
+/// `Handle<SkyboxMaterial>`. This includes the [`ClearColorSkybox`], if it's enabled. Use it with
+/// `with_run_criteria` to scope systems like sky controls or debug overlays to scenes with a
+/// skybox:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{has_skybox, SkyboxPlugin};
+/// # fn sky_controls() {}
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugin(SkyboxPlugin::default())
+///     .add_system(sky_controls.with_run_criteria(has_skybox))
+///     .run();
+/// ```
+pub fn has_skybox(skyboxes: Query<(), With<Handle<SkyboxMaterial>>>) -> ShouldRun {
+    if skyboxes.is_empty() {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+/// Debug-only system which warns (once per entity) about skyboxes which have been made the child
+/// of another entity. Skyboxes inherit their parent's rotation, which is almost never what you
+/// want.
+#[cfg(debug_assertions)]
+fn warn_parented_skyboxes(
+    skyboxes: Query<(Entity, &Parent), With<Handle<SkyboxMaterial>>>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    for (entity, parent) in skyboxes.iter() {
+        if warned.insert(entity) {
+            warn!(
+                "Skybox {:?} is a child of {:?}. Skyboxes inherit the rotation of their parent, \
+                 which is probably not what you want; spawn skyboxes without a parent instead.",
+                entity, parent.0,
+            );
         }
     }
 }
 
-/// Material for a Skybox. Consists of a base color and an optional 6-sided array-texture.
-///
-/// When rendering, the color from the texure is multiplied by the base color. This can be used to
-/// tint the skybox. When creating a new material, the default color is [`Color::WHITE`] which will
-/// have no effect on the texture color.
+/// Inserts the components [`SkyboxBundle`] would have added on skyboxes spawned without it, so
+/// they aren't frustum culled when the camera looks away from their origin and don't take part in
+/// shadows. Skyboxes marked with [`SkyboxCulled`], like those spawned with [`CulledSkyboxBundle`],
+/// are left to be culled. Bevy gives every mesh without [`NoFrustumCulling`] an [`Aabb`], so having
+/// one doesn't say anything about what the user wants. Logs the first time it has to fix up an
+/// entity.
+fn insert_skybox_markers(
+    mut commands: Commands,
+    skyboxes: Query<
+        (
+            Entity,
+            Option<&SkyboxCulled>,
+            Option<&NoFrustumCulling>,
+            Option<&NotShadowCaster>,
+            Option<&NotShadowReceiver>,
+        ),
+        (
+            With<Handle<SkyboxMaterial>>,
+            Or<(
+                (Without<NoFrustumCulling>, Without<SkyboxCulled>),
+                Without<NotShadowCaster>,
+                Without<NotShadowReceiver>,
+            )>,
+        ),
+    >,
+    mut logged: Local<bool>,
+) {
+    for (entity, culled, no_culling, not_caster, not_receiver) in skyboxes.iter() {
+        let mut skybox = commands.entity(entity);
+        let mut inserted = Vec::new();
+        if culled.is_none() && no_culling.is_none() {
+            skybox.insert(NoFrustumCulling);
+            inserted.push("NoFrustumCulling");
+        }
+        if not_caster.is_none() {
+            skybox.insert(NotShadowCaster);
+            inserted.push("NotShadowCaster");
+        }
+        if not_receiver.is_none() {
+            skybox.insert(NotShadowReceiver);
+            inserted.push("NotShadowReceiver");
+        }
+        if !*logged {
+            *logged = true;
+            info!(
+                "Skybox {:?} was spawned without {}; inserting them. Use SkyboxBundle to avoid this.",
+                entity,
+                inserted.join(", "),
+            );
+        }
+    }
+}
+
+/// Marker which hides a skybox while it is present, e.g. during cutscenes or in map views. Remove
+/// it to show the skybox again.
 ///
-/// It is also possible to use a skybox texture with only a [`Color`]. One reason you might want to
-/// do this is that (at time of writing) Bevy does not seem to antialias against the window
-/// [`ClearColor`] properly, instead antialiasing with white for objects that have not other 3d
-/// object behind them. This leads to white borders around antialiased object that overlap the
-/// window clear color. To avoid this, you could spawn a skybox using only a color. Since the skybox
-/// is a 3d rendered object, antialiasing against it works properly.
+/// This sets the skybox's [`Visibility`] when the marker is added or removed, before Bevy computes
+/// which entities are visible, so the skybox stops being drawn in the same frame. Removing the
+/// marker restores the visibility the skybox had when it was added, so skyboxes which were already
+/// hidden through `Visibility::is_visible` stay hidden.
 ///
-/// Skyboxes should generally be spawned using [`SkyboxBundle`], and you can see that type for info
-/// on what components are used with this material.
-#[derive(Debug, Clone, TypeUuid)]
-// UUID5 generated by first creating a URL-namespaced UUID5 for
-// "https://github.com/google/bevy_skybox_cubemap" (24291f52-ea01-574a-b6ae-3d8182f6086b) then using
-// that as the namespace with `bevy_skybox_cubemap::SkyboxMaterial` as the name.
-#[uuid = "fca7708e-57bb-5a81-977f-95b0e5202de0"]
-pub struct SkyboxMaterial {
-    /// Base color of the skybox. Multiplied with the color from the texture if a texture is
-    /// supplied, otherwise used by itself as the skybox color.
-    pub color: Color,
-    /// Texture to use for the skybox. This must be a an aray texture with 6 layers which are all
-    /// square and the same size. See [the crate overview](crate) for details on the required layer
-    /// order and how to get a texture in this format.
-    pub texture: Option<Handle<Image>>,
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxHidden, SkyboxMaterial};
+/// fn toggle_sky(
+///     mut commands: Commands,
+///     keys: Res<Input<KeyCode>>,
+///     skyboxes: Query<(Entity, Option<&SkyboxHidden>), With<Handle<SkyboxMaterial>>>,
+/// ) {
+///     if keys.just_pressed(KeyCode::H) {
+///         for (entity, hidden) in skyboxes.iter() {
+///             if hidden.is_some() {
+///                 commands.entity(entity).remove::<SkyboxHidden>();
+///             } else {
+///                 commands.entity(entity).insert(SkyboxHidden);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyboxHidden;
+
+/// Applies [`SkyboxHidden`] being added to or removed from skyboxes to their [`Visibility`],
+/// keeping the visibility each skybox had before it was hidden in `previous`.
+fn hide_skyboxes(
+    mut skyboxes: Query<&mut Visibility, With<Handle<SkyboxMaterial>>>,
+    hidden: Query<Entity, (With<Handle<SkyboxMaterial>>, Added<SkyboxHidden>)>,
+    shown: RemovedComponents<SkyboxHidden>,
+    mut previous: Local<HashMap<Entity, bool>>,
+) {
+    for entity in shown.iter() {
+        // Despawned skyboxes show up here too, which drops their entries.
+        let was_visible = previous.remove(&entity).unwrap_or(true);
+        if let Ok(mut visibility) = skyboxes.get_mut(entity) {
+            visibility.is_visible = was_visible;
+        }
+    }
+    for entity in hidden.iter() {
+        if let Ok(mut visibility) = skyboxes.get_mut(entity) {
+            previous.entry(entity).or_insert(visibility.is_visible);
+            visibility.is_visible = false;
+        }
+    }
 }
 
-#[derive(Clone)]
-pub struct GpuSkyboxMaterial {
-    _buffer: Buffer,
-    bind_group: BindGroup,
+/// Copies the [`SkyboxLayer`] of each skybox into the render world for [`order_skybox_layers`].
+fn extract_skybox_layers(
+    mut commands: Commands,
+    skyboxes: Query<(Entity, &SkyboxLayer), With<Handle<SkyboxMaterial>>>,
+) {
+    let layers: Vec<_> = skyboxes
+        .iter()
+        .map(|(entity, layer)| (entity, (*layer,)))
+        .collect();
+    commands.insert_or_spawn_batch(layers);
 }
 
-impl SkyboxMaterial {
-    /// Creates a `SkyboxMaterial` with just a texture. The color will be set to [`Color::WHITE`] to
-    /// avoid tinting the texture.
-    pub fn from_texture(texture: Handle<Image>) -> Self {
-        Self {
-            texture: Some(texture),
-            ..Default::default()
+/// Gives the skyboxes with a [`SkyboxLayer`] in each view's transparent phase the smallest sort
+/// distances, lowest layer first, so that the phase, which draws the farthest items first, draws
+/// them in layer order and before the scene's own transparent objects. This runs at the start of
+/// [`RenderStage::PhaseSort`], after everything has been queued and before the phase is sorted.
+fn order_skybox_layers(world: &mut World) {
+    let layers: HashMap<Entity, SkyboxLayer> = world
+        .query::<(Entity, &SkyboxLayer)>()
+        .iter(world)
+        .map(|(entity, layer)| (entity, *layer))
+        .collect();
+    if layers.is_empty() {
+        return;
+    }
+    for mut phase in world
+        .query::<&mut RenderPhase<Transparent3d>>()
+        .iter_mut(world)
+    {
+        let mut layered: Vec<_> = phase
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| Some((*layers.get(&item.entity)?, item.entity, index)))
+            .collect();
+        layered.sort_unstable();
+        for (rank, (_, _, index)) in layered.into_iter().enumerate() {
+            // Every one of these is farther than any real object, and they stay distinct.
+            phase.items[index].distance = f32::MIN / (rank + 1) as f32;
         }
     }
+}
 
-    /// Creates a `SkyboxMaterial` with only a color. This could be used in place of [`ClearColor`]
-    /// if `ClearColor` is giving you issues with antialiasing. Otherwise it's not all that useful.
-    pub fn from_color(color: Color) -> Self {
-        Self {
-            color,
-            ..Default::default()
+/// Keeps a skybox rotated so that the sun painted on its texture lines up with a
+/// [`DirectionalLight`], e.g. one which moves through the day.
+///
+/// Each frame, the skybox's rotation is replaced with the smallest rotation which takes
+/// `sky_sun_direction` to the direction the light is coming from. If the light entity is despawned
+/// or isn't a directional light, the skybox keeps its last rotation.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{AlignToLight, SkyboxBundle, SkyboxMaterial};
+/// fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+///     let sun = commands
+///         .spawn_bundle(DirectionalLightBundle::default())
+///         .id();
+///     commands
+///         .spawn_bundle(SkyboxBundle::new(skyboxes.add(SkyboxMaterial::default())))
+///         .insert(AlignToLight {
+///             light: sun,
+///             // The sun is painted low in the sky on the -Z (front) face.
+///             sky_sun_direction: Vec3::new(0.0, 0.3, -1.0),
+///         });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct AlignToLight {
+    /// The entity with the [`DirectionalLight`] to follow.
+    pub light: Entity,
+    /// Direction of the sun in the skybox texture, before the skybox is rotated.
+    pub sky_sun_direction: Vec3,
+}
+
+/// Rotates skyboxes with [`AlignToLight`] to match their light. This runs after transform
+/// propagation so it sees where the light is this frame, so it updates the skybox's
+/// [`GlobalTransform`] as well as its [`Transform`].
+fn align_skyboxes_to_lights(
+    mut skyboxes: Query<(&AlignToLight, &mut Transform, &mut GlobalTransform)>,
+    lights: Query<&GlobalTransform, (With<DirectionalLight>, Without<AlignToLight>)>,
+) {
+    for (align, mut transform, mut global_transform) in skyboxes.iter_mut() {
+        let light = match lights.get(align.light) {
+            Ok(light) => light,
+            Err(_) => continue,
+        };
+        let sky_sun = align.sky_sun_direction.normalize_or_zero();
+        // Directional lights shine along their forward direction, so the sun is behind them.
+        let to_sun = (light.rotation * Vec3::Z).normalize_or_zero();
+        if sky_sun == Vec3::ZERO || to_sun == Vec3::ZERO {
+            continue;
         }
+        let rotation = Quat::from_rotation_arc(sky_sun, to_sun);
+        transform.rotation = rotation;
+        global_transform.rotation = rotation;
     }
 }
 
-impl Default for SkyboxMaterial {
-    /// Creates a new skybox material with color set to white and no texture.
-    fn default() -> Self {
-        Self {
-            // Set the default color to white, so when using with a texture the color doesn't impact
-            // the texture color.
-            color: Color::WHITE,
-            texture: None,
+/// A looping motion of a skybox, for common sky animations without working out rotations by
+/// hand.
+///
+/// Each frame, the rotation of the skybox's [`Transform`] is replaced by the motion's rotation at
+/// the current time, so it shouldn't be combined with other ways of rotating the skybox, like
+/// [`AlignToLight`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxMotion};
+/// fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+///     commands
+///         .spawn_bundle(SkyboxBundle::new(skyboxes.add(SkyboxMaterial::default())))
+///         .insert(SkyboxMotion::SlowSpin { rpm: 0.5 });
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub enum SkyboxMotion {
+    /// Turns the sky around the vertical axis at a steady speed, in revolutions per minute.
+    /// Positive speeds turn it counterclockwise when seen from above.
+    SlowSpin {
+        /// Revolutions per minute.
+        rpm: f32,
+    },
+    /// Sways the sky back and forth around `axis`, up to `degrees` either way, taking `period`
+    /// seconds to go back and forth once.
+    Drift {
+        /// Axis to sway around.
+        axis: Vec3,
+        /// Largest angle the sky sways to either side, in degrees.
+        degrees: f32,
+        /// Seconds for one full sway there and back.
+        period: f32,
+    },
+}
+
+impl SkyboxMotion {
+    /// Rotation of the skybox `seconds` into the motion.
+    pub fn rotation_at(&self, seconds: f64) -> Quat {
+        use std::f64::consts::TAU;
+        match *self {
+            SkyboxMotion::SlowSpin { rpm } => {
+                let turns = (seconds * rpm as f64 / 60.0).fract();
+                Quat::from_rotation_y((turns * TAU) as f32)
+            }
+            SkyboxMotion::Drift {
+                axis,
+                degrees,
+                period,
+            } => {
+                let axis = axis.normalize_or_zero();
+                if axis == Vec3::ZERO || period <= 0.0 {
+                    return Quat::IDENTITY;
+                }
+                let phase = (seconds / period as f64).fract() * TAU;
+                Quat::from_axis_angle(axis, degrees.to_radians() * phase.sin() as f32)
+            }
         }
     }
 }
 
-impl RenderAsset for SkyboxMaterial {
-    type ExtractedAsset = SkyboxMaterial;
-    type PreparedAsset = GpuSkyboxMaterial;
-    type Param = (
-        SRes<RenderDevice>,
-        SRes<MaterialPipeline<Self>>,
-        SRes<RenderAssets<Image>>,
-    );
+/// Applies the [`SkyboxMotion`] of each skybox to its transform.
+fn animate_skybox_motion(time: Res<Time>, mut skyboxes: Query<(&SkyboxMotion, &mut Transform)>) {
+    let seconds = time.seconds_since_startup();
+    for (motion, mut transform) in skyboxes.iter_mut() {
+        transform.rotation = motion.rotation_at(seconds);
+    }
+}
 
-    fn extract_asset(&self) -> Self::ExtractedAsset {
-        self.clone()
+/// How a [`SkyboxRotationAnimation`] eases from one keyframe into the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkyboxEasing {
+    /// Turns at a steady speed.
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts quickly and slows down to settle on the keyframe.
+    EaseOut,
+    /// Starts slowly, speeds up, and slows down again to settle on the keyframe.
+    EaseInOut,
+}
+
+impl Default for SkyboxEasing {
+    fn default() -> Self {
+        SkyboxEasing::Linear
+    }
+}
+
+impl SkyboxEasing {
+    /// Maps the linear progress `t` between two keyframes, from 0 to 1, to the eased progress.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            SkyboxEasing::Linear => t,
+            SkyboxEasing::EaseIn => t * t,
+            SkyboxEasing::EaseOut => t * (2.0 - t),
+            SkyboxEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A keyframe of a [`SkyboxRotationAnimation`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyboxRotationKeyframe {
+    /// Seconds from the start of the animation at which the skybox reaches `rotation`.
+    pub time: f32,
+    /// Rotation of the skybox at this keyframe.
+    pub rotation: Quat,
+    /// How the skybox eases from the previous keyframe into this one.
+    pub easing: SkyboxEasing,
+}
+
+impl SkyboxRotationKeyframe {
+    /// Creates a keyframe which reaches `rotation` at `time` seconds, easing in with `easing`.
+    pub fn new(time: f32, rotation: Quat, easing: SkyboxEasing) -> Self {
+        Self {
+            time,
+            rotation,
+            easing,
+        }
+    }
+}
+
+/// A scripted rotation of a skybox, which turns the sky through a series of keyframes, for
+/// example to swing the sky around and let it settle during a camera reveal.
+///
+/// Each frame, `elapsed` is advanced by the frame time and the rotation of the skybox's
+/// [`Transform`] is replaced by the animation's rotation at that time, so it shouldn't be combined
+/// with other ways of rotating the skybox, like [`SkyboxMotion`]. Before the first keyframe the
+/// skybox has the first keyframe's rotation, and once a one-shot animation is over it keeps the
+/// last keyframe's rotation. Set `elapsed` back to 0 to play the animation again.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxEasing, SkyboxRotationAnimation, SkyboxRotationKeyframe};
+/// let turned = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+/// let animation = SkyboxRotationAnimation::new(vec![
+///     SkyboxRotationKeyframe::new(0.0, Quat::IDENTITY, SkyboxEasing::Linear),
+///     SkyboxRotationKeyframe::new(4.0, turned, SkyboxEasing::EaseOut),
+/// ]);
+/// assert!(animation.rotation_at(2.0).angle_between(Quat::IDENTITY) > 0.5);
+/// assert!(animation.rotation_at(10.0).abs_diff_eq(turned, 1e-6));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct SkyboxRotationAnimation {
+    /// The keyframes, in order of time.
+    pub keyframes: Vec<SkyboxRotationKeyframe>,
+    /// Whether the animation starts over once it reaches the last keyframe. It jumps straight back
+    /// to the first keyframe, so make the last keyframe's rotation the same as the first's for a
+    /// seamless loop.
+    pub looping: bool,
+    /// Seconds the animation has been playing for.
+    pub elapsed: f32,
+}
+
+impl SkyboxRotationAnimation {
+    /// Creates a one-shot animation through `keyframes`, which are sorted by time.
+    pub fn new(mut keyframes: Vec<SkyboxRotationKeyframe>) -> Self {
+        keyframes.sort_by(|a, b| {
+            a.time
+                .partial_cmp(&b.time)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Self {
+            keyframes,
+            looping: false,
+            elapsed: 0.0,
+        }
+    }
+
+    /// Makes the animation loop. See [`looping`](Self::looping).
+    pub fn looped(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Rotation of the skybox `seconds` into the animation.
+    pub fn rotation_at(&self, seconds: f32) -> Quat {
+        let (first, last) = match (self.keyframes.first(), self.keyframes.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Quat::IDENTITY,
+        };
+        let mut seconds = seconds;
+        if self.looping && last.time > 0.0 {
+            seconds = seconds.rem_euclid(last.time);
+        }
+        let next = match self.keyframes.iter().position(|key| key.time > seconds) {
+            Some(0) => return first.rotation,
+            Some(next) => next,
+            None => return last.rotation,
+        };
+        let (from, to) = (&self.keyframes[next - 1], &self.keyframes[next]);
+        let t = (seconds - from.time) / (to.time - from.time);
+        from.rotation
+            .normalize()
+            .slerp(to.rotation.normalize(), to.easing.ease(t))
+    }
+}
+
+/// Advances each [`SkyboxRotationAnimation`] and applies it to the skybox's transform.
+fn animate_skybox_rotations(
+    time: Res<Time>,
+    mut skyboxes: Query<(&mut SkyboxRotationAnimation, &mut Transform)>,
+) {
+    for (mut animation, mut transform) in skyboxes.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        // Keep looping animations' clocks small, so they don't lose precision over time.
+        let duration = animation.keyframes.last().map_or(0.0, |last| last.time);
+        if animation.looping && duration > 0.0 {
+            animation.elapsed = animation.elapsed.rem_euclid(duration);
+        }
+        transform.rotation = animation.rotation_at(animation.elapsed);
+    }
+}
+
+/// Turns a skybox toward a target rotation at a steady angular speed, for scripted transitions such
+/// as turning the sky to reveal the moon. The rotation of the skybox's [`Transform`] is moved toward
+/// `target` along the shortest arc each frame, and the component is removed once the target is
+/// reached, so inserting it again starts a new transition.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxMaterial, SkyboxRotateTo};
+/// fn reveal_moon(mut commands: Commands, skyboxes: Query<Entity, With<Handle<SkyboxMaterial>>>) {
+///     for skybox in skyboxes.iter() {
+///         // Turn the sky a quarter turn over 3 seconds.
+///         commands.entity(skybox).insert(SkyboxRotateTo {
+///             target: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+///             degrees_per_second: 30.0,
+///         });
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SkyboxRotateTo {
+    /// Rotation to end up at. Doesn't need to be normalized.
+    pub target: Quat,
+    /// How fast to turn, in degrees per second. Zero, negative, and NaN speeds snap straight to
+    /// the target.
+    pub degrees_per_second: f32,
+}
+
+/// Moves each skybox with a [`SkyboxRotateTo`] toward its target, removing the component once the
+/// target is reached.
+fn rotate_skyboxes_to_targets(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut skyboxes: Query<(Entity, &SkyboxRotateTo, &mut Transform)>,
+) {
+    for (entity, rotate_to, mut transform) in skyboxes.iter_mut() {
+        let target = rotate_to.target.normalize();
+        let remaining = transform.rotation.angle_between(target);
+        let speed = rotate_to.degrees_per_second.to_radians();
+        let step = speed * time.delta_seconds();
+        if speed.is_nan() || speed <= 0.0 || step >= remaining {
+            transform.rotation = target;
+            commands.entity(entity).remove::<SkyboxRotateTo>();
+        } else {
+            transform.rotation = transform.rotation.slerp(target, step / remaining);
+        }
+    }
+}
+
+/// Puts a skybox in a group whose members all share the rotation of the group's leader, to keep
+/// several skyboxes visually consistent, e.g. the skyboxes of mirrored views on different render
+/// layers. The number identifies the group.
+///
+/// Every frame, the rotation of the [`Transform`] of the group's [`SkyboxRotationLeader`] is copied
+/// to the other members, so rotate the leader, by hand or with components like [`SkyboxMotion`],
+/// and the rest follow. Members of a group without a leader keep their own rotations. If a group
+/// has more than one leader, one of them is picked arbitrarily.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::view::RenderLayers;
+/// # use bevy_skybox_cubemap::*;
+/// # fn setup(mut commands: Commands, material: Handle<SkyboxMaterial>) {
+/// commands
+///     .spawn_bundle(SkyboxBundle::new(material.clone()))
+///     .insert(SkyboxRotationGroup(0))
+///     .insert(SkyboxRotationLeader)
+///     .insert(SkyboxMotion::SlowSpin { rpm: 0.5 });
+/// commands
+///     .spawn_bundle(SkyboxBundle::new(material))
+///     .insert(SkyboxRotationGroup(0))
+///     .insert(RenderLayers::layer(1));
+/// # }
+/// ```
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SkyboxRotationGroup(pub u32);
+
+/// Marks the skybox whose rotation the other members of its [`SkyboxRotationGroup`] copy.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyboxRotationLeader;
+
+/// Copies the rotation of each [`SkyboxRotationGroup`]'s leader to the group's other members.
+fn sync_skybox_rotation_groups(
+    mut skyboxes: Query<(
+        &SkyboxRotationGroup,
+        Option<&SkyboxRotationLeader>,
+        &mut Transform,
+    )>,
+) {
+    let leaders: HashMap<SkyboxRotationGroup, Quat> = skyboxes
+        .iter()
+        .filter(|(_, leader, _)| leader.is_some())
+        .map(|(group, _, transform)| (*group, transform.rotation))
+        .collect();
+    if leaders.is_empty() {
+        return;
+    }
+    for (group, leader, mut transform) in skyboxes.iter_mut() {
+        if leader.is_some() {
+            continue;
+        }
+        if let Some(&rotation) = leaders.get(group) {
+            // Only write changed rotations, so unchanged transforms aren't marked as changed.
+            if transform.rotation != rotation {
+                transform.rotation = rotation;
+            }
+        }
+    }
+}
+
+/// Marker for the color-only skybox spawned automatically when
+/// [`SkyboxPlugin::auto_clear_color_fix`] is enabled.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct ClearColorSkybox;
+
+/// Internal marker for skyboxes which the plugin spawns and manages itself, as opposed to skyboxes
+/// authored by the user. [`manage_clear_color_skybox`] despawns them as soon as a user skybox
+/// exists, and makes sure there's never more than one of them.
+#[derive(Component, Debug, Default, Clone, Copy)]
+struct ManagedSkybox;
+
+/// Spawns, updates, and despawns the [`ClearColorSkybox`] so that it exists exactly when there is
+/// no other skybox. Skyboxes only on [`SKYBOX_REFLECTION_LAYER`] don't count, since the main view
+/// doesn't draw them. Any extra managed skyboxes, e.g. left behind by a scene which was saved and
+/// loaded again, are despawned.
+fn manage_clear_color_skybox(
+    mut commands: Commands,
+    clear_color: Res<ClearColor>,
+    user_skyboxes: Query<
+        Option<&RenderLayers>,
+        (
+            With<Handle<SkyboxMaterial>>,
+            Without<ClearColorSkybox>,
+            Without<ManagedSkybox>,
+        ),
+    >,
+    managed_skyboxes: Query<
+        (Entity, Option<&Handle<SkyboxMaterial>>),
+        Or<(With<ClearColorSkybox>, With<ManagedSkybox>)>,
+    >,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+) {
+    let reflection_only = RenderLayers::layer(SKYBOX_REFLECTION_LAYER);
+    if user_skyboxes
+        .iter()
+        .any(|layers| layers != Some(&reflection_only))
+    {
+        for (entity, _) in managed_skyboxes.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    // Keep the first managed skybox which still has a material, and despawn the rest.
+    let mut kept = None;
+    for (entity, handle) in managed_skyboxes.iter() {
+        match handle {
+            Some(handle) if kept.is_none() => kept = Some(handle),
+            _ => commands.entity(entity).despawn(),
+        }
+    }
+
+    match kept {
+        None => {
+            commands
+                .spawn_bundle(SkyboxBundle::new(
+                    materials.add(SkyboxMaterial::from_color(clear_color.0)),
+                ))
+                .insert(ClearColorSkybox)
+                .insert(ManagedSkybox);
+        }
+        Some(handle) if clear_color.is_changed() => {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color = clear_color.0;
+            }
+        }
+        Some(_) => {}
+    }
+}
+
+/// Resource for checking from the main world whether skybox materials have been prepared for
+/// rendering, i.e. their textures are loaded, converted, and uploaded to the GPU. Until then, a
+/// skybox isn't drawn at all, so this can be used to hold a loading screen until the sky is ready
+/// to avoid it popping in.
+///
+/// The [`SkyboxPlugin`] adds this resource. Since the renderer runs after the main world's
+/// systems, a material is reported ready starting from the frame after it was first drawn. To also
+/// know why a material isn't ready yet, see [`SkyboxStatus`].
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxMaterial, SkyboxReadiness};
+/// # struct LoadingScreen;
+/// fn hide_loading_screen(
+///     readiness: Res<SkyboxReadiness>,
+///     skyboxes: Query<&Handle<SkyboxMaterial>>,
+///     mut loading_screens: Query<&mut Visibility, With<LoadingScreen>>,
+/// ) {
+///     if skyboxes.iter().all(|skybox| readiness.is_ready(skybox)) {
+///         for mut visibility in loading_screens.iter_mut() {
+///             visibility.is_visible = false;
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct SkyboxReadiness {
+    /// Materials which have been prepared. Shared between the main world and the render world.
+    prepared: Arc<RwLock<HashSet<HandleId>>>,
+}
+
+impl SkyboxReadiness {
+    /// Checks whether the given skybox material has been prepared for rendering.
+    pub fn is_ready(&self, handle: &Handle<SkyboxMaterial>) -> bool {
+        self.prepared.read().unwrap().contains(&handle.id)
+    }
+}
+
+/// Component which the [`SkyboxPlugin`] adds to every skybox entity, saying whether its material
+/// is drawn with all of its textures this frame. This combines the load state of the material and
+/// its textures, whether they are still waiting in [`SkyboxTextureConversion`], and
+/// [`SkyboxReadiness`] into one check:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::SkyboxStatus;
+/// fn sky_loaded(skyboxes: Query<&SkyboxStatus>) -> bool {
+///     skyboxes.iter().all(|status| *status == SkyboxStatus::Ready)
+/// }
+/// ```
+///
+/// It is updated every frame, after textures are converted. Like `SkyboxReadiness`, it only
+/// becomes [`Ready`](SkyboxStatus::Ready) the frame after the material was first prepared.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxStatus {
+    /// The material or one of its textures hasn't finished loading.
+    Loading,
+    /// One of the material's textures is loaded but is waiting to be converted.
+    Converting,
+    /// Everything is loaded, but the material hasn't been prepared for rendering yet.
+    Preparing,
+    /// The material is drawn with all of its textures.
+    Ready,
+    /// The material or one of its textures failed to load, so it will never be ready.
+    Failed,
+}
+
+/// Computes the [`SkyboxStatus`] of every skybox, inserting the component where it's missing.
+fn update_skybox_status(
+    mut commands: Commands,
+    mut skyboxes: Query<(Entity, &Handle<SkyboxMaterial>, Option<&mut SkyboxStatus>)>,
+    materials: Res<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    conversions: Option<Res<SkyboxTextureConversion>>,
+    gpu_conversion: Option<Res<SkyboxGpuConversion>>,
+    readiness: Res<SkyboxReadiness>,
+) {
+    for (entity, handle, status) in skyboxes.iter_mut() {
+        let new_status = match materials.get(handle) {
+            Some(material) => {
+                let mut new_status = SkyboxStatus::Preparing;
+                for texture in [
+                    &material.texture,
+                    &material.overlay_texture,
+                    &material.lut,
+                    &material.low_res_texture,
+                    &material.mask_texture,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    // Textures converted on the GPU only exist in the render world.
+                    match gpu_conversion
+                        .as_ref()
+                        .and_then(|conversion| conversion.is_converted(texture))
+                    {
+                        Some(true) => continue,
+                        Some(false) => {
+                            if new_status == SkyboxStatus::Preparing {
+                                new_status = SkyboxStatus::Converting;
+                            }
+                            continue;
+                        }
+                        None => {}
+                    }
+                    if asset_server.get_load_state(texture) == LoadState::Failed {
+                        new_status = SkyboxStatus::Failed;
+                        break;
+                    } else if images.get(texture).is_none() {
+                        // Textures being converted asynchronously are out of `images` meanwhile.
+                        let conversions = conversions.as_ref();
+                        if conversions.map_or(false, |conversions| conversions.has_failed(texture))
+                        {
+                            new_status = SkyboxStatus::Failed;
+                            break;
+                        } else if conversions
+                            .map_or(false, |conversions| conversions.is_queued(texture))
+                        {
+                            if new_status == SkyboxStatus::Preparing {
+                                new_status = SkyboxStatus::Converting;
+                            }
+                        } else {
+                            new_status = SkyboxStatus::Loading;
+                        }
+                    } else if new_status == SkyboxStatus::Preparing
+                        && conversions
+                            .as_ref()
+                            .map_or(false, |conversions| conversions.is_queued(texture))
+                    {
+                        new_status = SkyboxStatus::Converting;
+                    }
+                }
+                if new_status == SkyboxStatus::Preparing && readiness.is_ready(handle) {
+                    SkyboxStatus::Ready
+                } else {
+                    new_status
+                }
+            }
+            None if asset_server.get_load_state(handle) == LoadState::Failed => {
+                SkyboxStatus::Failed
+            }
+            None => SkyboxStatus::Loading,
+        };
+        match status {
+            // Only write the status when it changes, so it can be watched with `Changed`.
+            Some(mut status) => {
+                if *status != new_status {
+                    *status = new_status;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(new_status);
+            }
+        }
+    }
+}
+
+/// Render world system which records which skybox materials have been prepared in
+/// [`SkyboxReadiness`].
+fn update_skybox_readiness(
+    readiness: Res<SkyboxReadiness>,
+    materials: Res<RenderAssets<SkyboxMaterial>>,
+) {
+    let mut prepared = readiness.prepared.write().unwrap();
+    prepared.clear();
+    prepared.extend(materials.keys().map(|handle| handle.id));
+}
+
+/// [`Diagnostic`] measuring the GPU memory used by the textures of prepared skybox materials, in
+/// megabytes. This adds up the size of every mip level of each material's texture, overlay, color
+/// grading LUT, low resolution texture and mask texture, plus the cube copy of textures with
+/// [`SkyboxMaterial::cube_sampling`]. Textures shared between materials are counted once, and the
+/// crate's own placeholder textures aren't counted.
+///
+/// The [`SkyboxPlugin`] measures it every frame as long as Bevy's `DiagnosticsPlugin` is added
+/// (which `DefaultPlugins` does), so it can be logged with the other diagnostics:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::diagnostic::LogDiagnosticsPlugin;
+/// # use bevy_skybox_cubemap::{SkyboxPlugin, SKYBOX_VRAM_DIAGNOSTIC};
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugin(SkyboxPlugin::default())
+///     .add_plugin(LogDiagnosticsPlugin::filtered(vec![SKYBOX_VRAM_DIAGNOSTIC]))
+///     .run();
+/// ```
+pub const SKYBOX_VRAM_DIAGNOSTIC: DiagnosticId =
+    DiagnosticId::from_u128(259112402311748063845619227806932183597);
+
+/// Registers [`SKYBOX_VRAM_DIAGNOSTIC`], if diagnostics are enabled.
+fn setup_skybox_diagnostics(diagnostics: Option<ResMut<Diagnostics>>) {
+    if let Some(mut diagnostics) = diagnostics {
+        diagnostics
+            .add(Diagnostic::new(SKYBOX_VRAM_DIAGNOSTIC, "skybox_vram", 20).with_suffix("MB"));
+    }
+}
+
+/// Measures [`SKYBOX_VRAM_DIAGNOSTIC`] from the textures of the materials in [`SkyboxReadiness`].
+fn measure_skybox_vram(
+    diagnostics: Option<ResMut<Diagnostics>>,
+    materials: Res<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+    readiness: Res<SkyboxReadiness>,
+) {
+    let mut diagnostics = match diagnostics {
+        Some(diagnostics) => diagnostics,
+        None => return,
+    };
+    let prepared = readiness.prepared.read().unwrap();
+    // Textures, and cube copies of one of their frames.
+    let mut counted: HashSet<(HandleId, Option<u32>)> = HashSet::default();
+    let mut bytes = 0;
+    for (id, material) in materials.iter() {
+        if !prepared.contains(&id) {
+            continue;
+        }
+        for texture in [
+            &material.texture,
+            &material.overlay_texture,
+            &material.lut,
+            &material.low_res_texture,
+            &material.mask_texture,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !counted.insert((texture.id, None)) {
+                continue;
+            }
+            if let Some(image) = images.get(texture) {
+                bytes += texture_size_in_bytes(&image.texture_descriptor);
+            }
+        }
+        // Cube copies have a single frame and no mipmaps, and are shared by every material
+        // sampling the same frame of a texture as a cube.
+        if let Some(image) = material
+            .texture
+            .as_ref()
+            .filter(|texture| {
+                material.cube_sampling && counted.insert((texture.id, Some(material.frame)))
+            })
+            .and_then(|texture| images.get(texture))
+        {
+            let mut cube = image.texture_descriptor.clone();
+            cube.size.depth_or_array_layers = 6;
+            cube.mip_level_count = 1;
+            bytes += texture_size_in_bytes(&cube);
+        }
+    }
+    diagnostics.add_measurement(SKYBOX_VRAM_DIAGNOSTIC, bytes as f64 / 1_000_000.0);
+}
+
+/// Number of bytes a texture with the given descriptor takes up on the GPU, including all of its
+/// mip levels.
+fn texture_size_in_bytes(descriptor: &TextureDescriptor) -> u64 {
+    let info = descriptor.format.describe();
+    let (block_width, block_height) = (
+        info.block_dimensions.0 as u64,
+        info.block_dimensions.1 as u64,
+    );
+    let size = descriptor.size;
+    let mut bytes = 0;
+    for level in 0..descriptor.mip_level_count.max(1) {
+        let width = (size.width as u64 >> level).max(1);
+        let height = (size.height as u64 >> level).max(1);
+        // Array layers stay the same at every level, but 3d textures shrink in depth too.
+        let layers = match descriptor.dimension {
+            TextureDimension::D3 => (size.depth_or_array_layers as u64 >> level).max(1),
+            _ => size.depth_or_array_layers as u64,
+        };
+        let blocks = ((width + block_width - 1) / block_width)
+            * ((height + block_height - 1) / block_height);
+        bytes += blocks * info.block_size as u64 * layers;
+    }
+    bytes
+}
+
+/// Marker for the camera which systems of this crate that need "the" camera should use, such as the
+/// one drawing [`SkyboxDebugEdges`]. Without a marked camera they use the first camera with a
+/// perspective projection, which is arbitrary when there is more than one; add this to one of your
+/// cameras to choose deterministically.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyboxCamera;
+
+/// Query for the cameras considered by [`skybox_camera`].
+type SkyboxCameraQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static GlobalTransform,
+        Option<&'static SkyboxCamera>,
+        Option<&'static PerspectiveProjection>,
+    ),
+    With<Camera>,
+>;
+
+/// Picks the camera marked with [`SkyboxCamera`], or the first perspective camera if none is marked.
+fn skybox_camera<'a>(cameras: &'a SkyboxCameraQuery) -> Option<&'a GlobalTransform> {
+    let mut fallback = None;
+    for (transform, marker, perspective) in cameras.iter() {
+        if marker.is_some() {
+            return Some(transform);
+        }
+        if fallback.is_none() && perspective.is_some() {
+            fallback = Some(transform);
+        }
+    }
+    fallback
+}
+
+/// Resource which switches every skybox to a debug view that ignores the texture and colors each
+/// face by the axis it represents. This makes it easy to check whether the faces of a skybox end up
+/// where you expect and whether any of them are rotated.
+///
+/// Positive faces are drawn in a bright color and negative faces in a dark one: red for X, green for
+/// Y, and blue for Z. Each face has one stripe across its middle per layer index (so +X has 1 stripe
+/// and -Z has 6), and a white square marks the corner where the face's UV origin is.
+///
+/// The [`SkyboxPlugin`] adds this resource, disabled by default.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::SkyboxDebugFaces;
+/// fn toggle_debug_faces(keys: Res<Input<KeyCode>>, mut debug: ResMut<SkyboxDebugFaces>) {
+///     if keys.just_pressed(KeyCode::F1) {
+///         debug.0 = !debug.0;
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SkyboxDebugFaces(pub bool);
+
+/// Resource which draws the edges of the cube the skybox is mapped onto, as lines around the camera.
+/// The lines follow the rotation of each skybox's transform, which shows where the boundaries
+/// between faces end up in the world. Edges running along the X axis are red, along Y are green,
+/// and along Z are blue.
+///
+/// The [`SkyboxPlugin`] adds this resource, disabled by default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyboxDebugEdges {
+    /// Whether to draw the edges.
+    pub enabled: bool,
+    /// Distance from the camera to each face of the drawn cube. This needs to be between the
+    /// camera's near and far planes.
+    pub distance: f32,
+}
+
+impl Default for SkyboxDebugEdges {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            distance: 100.0,
+        }
+    }
+}
+
+/// Root of the line meshes drawn for a skybox by [`SkyboxDebugEdges`].
+#[derive(Component)]
+struct SkyboxDebugEdgesRoot {
+    skybox: Entity,
+}
+
+/// Spawns, positions, and despawns the lines drawn by [`SkyboxDebugEdges`].
+#[allow(clippy::too_many_arguments)]
+fn draw_skybox_debug_edges(
+    mut commands: Commands,
+    settings: Res<SkyboxDebugEdges>,
+    skyboxes: Query<(Entity, &GlobalTransform), With<Handle<SkyboxMaterial>>>,
+    cameras: SkyboxCameraQuery,
+    mut roots: Query<(Entity, &SkyboxDebugEdgesRoot, &mut Transform)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut edge_assets: Local<Option<[(Handle<Mesh>, Handle<StandardMaterial>); 3]>>,
+) {
+    if !settings.enabled {
+        for (entity, _, _) in roots.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let camera_position = skybox_camera(&cameras).map_or(Vec3::ZERO, |camera| camera.translation);
+    let mut drawn = HashSet::default();
+    for (entity, root, mut transform) in roots.iter_mut() {
+        match skyboxes.get(root.skybox) {
+            Ok((_, skybox_transform)) => {
+                *transform = Transform {
+                    translation: camera_position,
+                    rotation: skybox_transform.rotation,
+                    scale: Vec3::splat(settings.distance),
+                };
+                drawn.insert(root.skybox);
+            }
+            Err(_) => commands.entity(entity).despawn_recursive(),
+        }
+    }
+
+    for (skybox, skybox_transform) in skyboxes.iter() {
+        if drawn.contains(&skybox) {
+            continue;
+        }
+        let edge_assets = edge_assets.get_or_insert_with(|| {
+            let colors = [Color::RED, Color::GREEN, Color::BLUE];
+            [0, 1, 2].map(|axis| {
+                (
+                    meshes.add(cube_edges_mesh(axis)),
+                    materials.add(StandardMaterial {
+                        base_color: colors[axis],
+                        unlit: true,
+                        ..Default::default()
+                    }),
+                )
+            })
+        });
+        commands
+            .spawn_bundle((
+                SkyboxDebugEdgesRoot { skybox },
+                Transform {
+                    translation: camera_position,
+                    rotation: skybox_transform.rotation,
+                    scale: Vec3::splat(settings.distance),
+                },
+                GlobalTransform::default(),
+            ))
+            .with_children(|parent| {
+                for (mesh, material) in edge_assets.iter() {
+                    parent
+                        .spawn_bundle(PbrBundle {
+                            mesh: mesh.clone(),
+                            material: material.clone(),
+                            ..Default::default()
+                        })
+                        .insert(NotShadowCaster)
+                        .insert(NotShadowReceiver);
+                }
+            });
+    }
+}
+
+/// Builds a line mesh of the 4 edges of a unit cube (centered on the origin, with faces at +/-1)
+/// which run parallel to the given axis.
+fn cube_edges_mesh(axis: usize) -> Mesh {
+    let mut positions = Vec::with_capacity(8);
+    for (a, b) in [(-1.0, -1.0), (-1.0, 1.0), (1.0, -1.0), (1.0, 1.0)] {
+        for end in [-1.0, 1.0] {
+            let mut position = [0.0f32; 3];
+            position[axis] = end;
+            position[(axis + 1) % 3] = a;
+            position[(axis + 2) % 3] = b;
+            positions.push(position);
+        }
+    }
+    let len = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0f32, 1.0, 0.0]; len]);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0f32, 0.0]; len]);
+    mesh
+}
+
+/// Resource which selects between the regular and low resolution textures of skybox materials, for
+/// scaling down on less capable devices. See [`SkyboxMaterial::low_res_texture`].
+///
+/// The [`SkyboxPlugin`] adds this resource, set to [`SkyboxQuality::High`] by default. It can be
+/// changed at any time, and skyboxes fade smoothly between their textures over half a second when
+/// it does. Both textures need to be loaded for the fade; until they are, the skybox keeps showing
+/// the texture it had.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxQuality {
+    /// Use the low resolution texture of materials which have one.
+    Low,
+    /// Always use the regular texture.
+    High,
+}
+
+impl Default for SkyboxQuality {
+    fn default() -> Self {
+        SkyboxQuality::High
+    }
+}
+
+/// Resource for scene-wide effects which change how every skybox looks, like the sky being seen
+/// through water or fog. The output of each skybox is mixed toward `tint` by `density`, on top of
+/// the material's own color settings.
+///
+/// The [`SkyboxPlugin`] adds this resource with a density of 0, which leaves skyboxes unchanged.
+/// It can be changed at any time, e.g. from a system which raises the density while the camera is
+/// underwater; all skybox materials are prepared again when it changes.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::SkyboxEnvironment;
+/// # struct Submerged(bool);
+/// fn murky_water(
+///     time: Res<Time>,
+///     submerged: Res<Submerged>,
+///     mut environment: ResMut<SkyboxEnvironment>,
+/// ) {
+///     let target = if submerged.0 { 0.8 } else { 0.0 };
+///     let step = time.delta_seconds() * 2.0;
+///     if environment.density != target {
+///         environment.tint = Color::rgb(0.05, 0.2, 0.3);
+///         environment.density = if target > environment.density {
+///             (environment.density + step).min(target)
+///         } else {
+///             (environment.density - step).max(target)
+///         };
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyboxEnvironment {
+    /// Color of the medium the sky is seen through.
+    pub tint: Color,
+    /// How much of the sky is replaced by `tint`, from 0 (none) to 1 (only the tint).
+    pub density: f32,
+}
+
+impl Default for SkyboxEnvironment {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            density: 0.0,
+        }
+    }
+}
+
+/// Textures of skybox materials which failed to load or don't exist, for
+/// [`SkyboxPlugin::debug_missing_pattern`]. Only added when that is turned on.
+#[derive(Debug, Default, Clone)]
+struct SkyboxMissingTextures(HashSet<HandleId>);
+
+/// Render world copy of [`SkyboxMissingTextures`]. Only present when
+/// [`SkyboxPlugin::debug_missing_pattern`] is turned on.
+#[derive(Debug, Default, Clone)]
+struct SkyboxMissingPattern(HashSet<HandleId>);
+
+/// Updates [`SkyboxMissingTextures`], and prepares the materials using textures which went missing
+/// or turned up again, so they switch between the pattern and the texture.
+fn find_missing_skybox_textures(
+    mut missing: ResMut<SkyboxMissingTextures>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    conversions: Option<Res<SkyboxTextureConversion>>,
+    gpu_conversion: Option<Res<SkyboxGpuConversion>>,
+) {
+    let is_missing = |texture: &Handle<Image>| {
+        // Textures converted on the GPU are only in the render world, and asynchronous
+        // conversions take textures out of `images` while they run.
+        if images.contains(texture)
+            || gpu_conversion.as_ref().map_or(false, |conversion| {
+                conversion.is_converted(texture).is_some()
+            })
+        {
+            return false;
+        }
+        if let Some(conversions) = &conversions {
+            if conversions.has_failed(texture) {
+                return true;
+            }
+            // Textures added with `images.add` are never loaded, so one being converted
+            // asynchronously would otherwise look missing until it's back.
+            if conversions.is_queued(texture) {
+                return false;
+            }
+        }
+        matches!(
+            asset_server.get_load_state(texture),
+            LoadState::Failed | LoadState::NotLoaded | LoadState::Unloaded
+        )
+    };
+    let now_missing: HashSet<_> = materials
+        .iter()
+        .filter_map(|(_, material)| material.texture.as_ref())
+        .filter(|texture| is_missing(texture))
+        .map(|texture| texture.id)
+        .collect();
+    if now_missing == missing.0 {
+        return;
+    }
+    let changed: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| {
+            material.texture.as_ref().map_or(false, |texture| {
+                now_missing.contains(&texture.id) != missing.0.contains(&texture.id)
+            })
+        })
+        .map(|(id, _)| id)
+        .collect();
+    for id in changed {
+        materials.get_mut(id);
+    }
+    missing.0 = now_missing;
+}
+
+/// How long fading between the regular and low resolution textures takes when the
+/// [`SkyboxQuality`] changes.
+const SKYBOX_LOD_FADE_SECONDS: f32 = 0.5;
+
+/// How far through the fade from the regular textures to the low resolution textures skyboxes are,
+/// from 0 (regular) to 1 (low resolution).
+#[derive(Debug, Default, Clone, Copy)]
+struct SkyboxLodBlend(f32);
+
+/// Moves [`SkyboxLodBlend`] toward the current [`SkyboxQuality`], and prepares the materials with
+/// low resolution textures again as it changes.
+fn animate_skybox_lod(
+    time: Res<Time>,
+    quality: Res<SkyboxQuality>,
+    mut blend: ResMut<SkyboxLodBlend>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+    mut started: Local<bool>,
+) {
+    let target = match *quality {
+        SkyboxQuality::Low => 1.0,
+        SkyboxQuality::High => 0.0,
+    };
+    if !*started {
+        // Start out at the initial quality rather than fading to it.
+        *started = true;
+        blend.0 = target;
+    }
+    if blend.0 == target {
+        return;
+    }
+    let step = time.delta_seconds() / SKYBOX_LOD_FADE_SECONDS;
+    let new_blend = if target > blend.0 {
+        (blend.0 + step).min(target)
+    } else {
+        (blend.0 - step).max(target)
+    };
+    // Only write to materials when the blend moved, since writing prepares them again.
+    if new_blend == blend.0 {
+        return;
+    }
+    blend.0 = new_blend;
+    let ids: Vec<_> = materials
+        .iter()
+        .filter(|(_, material)| material.low_res_texture.is_some())
+        .map(|(id, _)| id)
+        .collect();
+    for id in ids {
+        materials.get_mut(id);
+    }
+}
+
+/// How far the textures of materials with [`SkyboxMaterial::fade_in_seconds`] have faded in, from
+/// 0 to 1, by texture. Textures which haven't started fading yet aren't included.
+#[derive(Debug, Default, Clone)]
+struct SkyboxFades(HashMap<HandleId, f32>);
+
+/// How far a texture in [`SkyboxFades`] has faded in. Textures which haven't started fading yet
+/// are still faded out.
+fn skybox_fade(fades: Option<&SkyboxFades>, texture: HandleId) -> f32 {
+    fades
+        .and_then(|fades| fades.0.get(&texture).copied())
+        .unwrap_or(0.0)
+}
+
+/// Starts fading in materials with [`SkyboxMaterial::fade_in_seconds`] once they are first ready,
+/// and updates [`SkyboxFades`], which [`update_skybox_uniforms`] writes into the prepared
+/// materials.
+fn fade_in_skyboxes(
+    time: Res<Time>,
+    readiness: Res<SkyboxReadiness>,
+    mut fades: ResMut<SkyboxFades>,
+    materials: Res<Assets<SkyboxMaterial>>,
+    mut started: Local<HashMap<HandleId, f64>>,
+) {
+    let now = time.seconds_since_startup();
+    for (id, material) in materials.iter() {
+        let texture = match &material.texture {
+            Some(texture) if material.fade_in_seconds > 0.0 => texture.id,
+            _ => continue,
+        };
+        if fades.0.get(&texture) == Some(&1.0) {
+            continue;
+        }
+        if !readiness.is_ready(&Handle::weak(id)) {
+            continue;
+        }
+        let start = *started.entry(texture).or_insert(now);
+        let t = (((now - start) as f32) / material.fade_in_seconds).min(1.0);
+        // Smoothstep, so the fade eases in and out.
+        fades.0.insert(texture, t * t * (3.0 - 2.0 * t));
+    }
+}
+
+/// Seconds since startup, wrapped to [`SKYBOX_TIME_PERIOD`], extracted into the render world each
+/// frame for materials with [`SkyboxMaterial::twinkle`].
+#[derive(Debug, Default, Clone, Copy)]
+struct SkyboxTime(f32);
+
+/// Period after which [`SkyboxTime`] wraps back to zero. An `f32` holding seconds since startup
+/// loses too much precision after a few days for the twinkling to stay smooth, so the time is
+/// wrapped while it is still an `f64`, at the cost of a jump in the twinkling once an hour.
+const SKYBOX_TIME_PERIOD: f64 = 3600.0;
+
+/// Writes the current [`SkyboxTime`] into the uniform buffers of prepared materials with
+/// [`SkyboxMaterial::twinkle`], and the current [`SkyboxFades`] into those of materials which are
+/// fading in. Changing the material assets instead would prepare them again every frame, with a
+/// new buffer and bind group each time.
+fn update_skybox_uniforms(
+    render_queue: Res<RenderQueue>,
+    time: Option<Res<SkyboxTime>>,
+    fades: Option<Res<SkyboxFades>>,
+    mut materials: ResMut<RenderAssets<SkyboxMaterial>>,
+) {
+    let time = time.map_or(0.0, |time| time.0);
+    for material in materials.values_mut() {
+        let uniform = &mut material.uniform;
+        let new_time = if uniform.flags & SKYBOX_FLAGS_TWINKLE != 0 {
+            time
+        } else {
+            uniform.time
+        };
+        let new_fade = material.fade_texture.map_or(uniform.fade, |texture| {
+            skybox_fade(fades.as_deref(), texture)
+        });
+        if new_time == uniform.time && new_fade == uniform.fade {
+            continue;
+        }
+        uniform.time = new_time;
+        uniform.fade = new_fade;
+        render_queue.write_buffer(&material.buffer, 0, uniform.as_std140().as_bytes());
+    }
+}
+
+/// Adds [`TextureUsages::COPY_SRC`] to the textures of materials with
+/// [`SkyboxMaterial::cube_sampling`], so they can be copied into a cube texture. This runs as soon
+/// as a texture is loaded, before it is first uploaded to the GPU.
+fn allow_skybox_cube_copies(
+    materials: Res<Assets<SkyboxMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (_, material) in materials.iter() {
+        let texture = match &material.texture {
+            Some(texture) if material.cube_sampling => texture,
+            _ => continue,
+        };
+        let needs_copy_src = images.get(texture).map_or(false, |image| {
+            !image
+                .texture_descriptor
+                .usage
+                .contains(TextureUsages::COPY_SRC)
+        });
+        if needs_copy_src {
+            if let Some(image) = images.get_mut(texture) {
+                image.texture_descriptor.usage |= TextureUsages::COPY_SRC;
+            }
+        }
+    }
+}
+
+/// Copies app-wide skybox settings into the render world so they are available when preparing
+/// skybox materials.
+fn extract_skybox_settings(
+    mut commands: Commands,
+    debug: Res<SkyboxDebugFaces>,
+    lod_blend: Res<SkyboxLodBlend>,
+    fades: Res<SkyboxFades>,
+    environment: Res<SkyboxEnvironment>,
+    time: Res<Time>,
+    missing: Option<Res<SkyboxMissingTextures>>,
+) {
+    commands.insert_resource(*debug);
+    if let Some(missing) = missing {
+        commands.insert_resource(SkyboxMissingPattern(missing.0.clone()));
+    }
+    commands.insert_resource(*environment);
+    commands.insert_resource(*lod_blend);
+    commands.insert_resource(fades.clone());
+    let seconds = time.seconds_since_startup() % SKYBOX_TIME_PERIOD;
+    commands.insert_resource(SkyboxTime(seconds as f32));
+}
+
+/// Render world resource with the descriptors of the textures used by skybox materials. The render
+/// world's images don't record their format or size, but preparing a material needs them to check
+/// whether the texture can be filtered and to copy it into a cube texture.
+#[derive(Default)]
+struct SkyboxTextureDescriptors(HashMap<HandleId, TextureDescriptor<'static>>);
+
+/// Copies the descriptors of skybox textures into [`SkyboxTextureDescriptors`].
+fn extract_skybox_texture_descriptors(
+    mut commands: Commands,
+    materials: Res<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+    gpu_conversion: Option<Res<SkyboxGpuConversion>>,
+) {
+    let mut descriptors = gpu_conversion.map_or_else(HashMap::default, |conversion| {
+        conversion.state.read().unwrap().targets.clone()
+    });
+    for (_, material) in materials.iter() {
+        for texture in [&material.texture, &material.low_res_texture]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(image) = images.get(texture) {
+                descriptors.insert(texture.id, image.texture_descriptor.clone());
+            }
+        }
+    }
+    commands.insert_resource(SkyboxTextureDescriptors(descriptors));
+}
+
+/// App-wide settings are baked into the prepared material, so when they change all skybox materials
+/// need to be prepared again. Touching each material mutably is enough to make that happen.
+fn refresh_skybox_materials(
+    debug: Res<SkyboxDebugFaces>,
+    environment: Res<SkyboxEnvironment>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+) {
+    let debug_changed = debug.is_changed() && !debug.is_added();
+    let environment_changed = environment.is_changed() && !environment.is_added();
+    if !debug_changed && !environment_changed {
+        return;
+    }
+    let ids: Vec<_> = materials.ids().collect();
+    for id in ids {
+        materials.get_mut(id);
+    }
+}
+
+/// Bundle for spawning Skybox entities. Note that you should be able to use defaults for everything
+/// besides `material`. The only other field you may want to touch is `transform` which can be used
+/// to rotate the skybox if desired. Translations applied to skyboxes are ignored.
+///
+/// When inserting a skybox bundle, you should generally use `..Default::default()` for every
+/// property except the `material` and occasionally `transform` (if you want to rotate the skybox
+/// from its default orientation).
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxPlugin, SkyboxTextureConversion};
+/// # App::new()
+/// #     .add_startup_system(setup.system());
+/// # fn setup(
+/// #     mut commands: Commands,
+/// #     asset_server: Res<AssetServer>,
+/// #     mut meshes: ResMut<Assets<Mesh>>,
+/// #     mut materials: ResMut<Assets<StandardMaterial>>,
+/// #     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+/// #     mut skybox_conversion: ResMut<SkyboxTextureConversion>,
+/// # ) {
+/// # let skybox_texture = asset_server.load("labeled_skybox.png");
+/// # skybox_conversion.make_array(skybox_texture.clone());
+/// commands.spawn_bundle(SkyboxBundle::new(
+///     skyboxes.add(SkyboxMaterial::from_texture(skybox_texture)),
+/// ));
+/// # }
+/// ```
+///
+/// Skyboxes can be spawned before any camera exists, e.g. while a level is loading. Nothing in the
+/// crate needs a camera: the skybox is simply not drawn until a camera that can see it appears,
+/// and the systems which use the camera's position skip their work until then. Without a renderer,
+/// such as in this headless app, the skybox gets as far as waiting to be prepared for rendering:
+///
+/// ```
+/// # use bevy::{asset::AssetPlugin, pbr::AmbientLight, render::render_resource::Shader};
+/// # use bevy::transform::TransformPlugin;
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxPlugin, SkyboxStatus};
+/// # let mut app = App::new();
+/// # app.add_plugins(MinimalPlugins)
+/// #     .add_plugin(AssetPlugin)
+/// #     .add_plugin(TransformPlugin)
+/// #     .add_asset::<Shader>()
+/// #     .add_asset::<Mesh>()
+/// #     .add_asset::<Image>()
+/// #     .add_asset::<StandardMaterial>()
+/// #     .init_resource::<AmbientLight>()
+/// #     .add_plugin(SkyboxPlugin::default());
+/// # let material = app
+/// #     .world
+/// #     .get_resource_mut::<Assets<SkyboxMaterial>>()
+/// #     .unwrap()
+/// #     .add(SkyboxMaterial::from_color(Color::BLUE));
+/// let skybox = app.world.spawn().insert_bundle(SkyboxBundle::new(material)).id();
+/// app.update();
+/// assert_eq!(app.world.get::<SkyboxStatus>(skybox), Some(&SkyboxStatus::Preparing));
+///
+/// app.world.spawn().insert_bundle(PerspectiveCameraBundle::new_3d());
+/// app.update();
+/// assert_eq!(app.world.get::<SkyboxStatus>(skybox), Some(&SkyboxStatus::Preparing));
+/// ```
+#[derive(Bundle)]
+pub struct SkyboxBundle {
+    /// Material to use for the skybox. Defaults to a garish pink. In most usage this should be the
+    /// only field you need to set.
+    pub material: Handle<SkyboxMaterial>,
+    /// Mesh to use for the skybox. Defaults to [`SKYBOX_MESH_HANDLE`], which is a unit cube. You
+    /// shouldn't ever need to use any other mesh. Because of how cubemap sampling works, probably
+    /// any mesh that completely surrounds the camera would work equally well, but only the unit
+    /// cube is officially supported by this crate.
+    pub mesh: Handle<Mesh>,
+    /// User indication of whether the skybox is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+    pub no_frustum_culling: NoFrustumCulling,
+    pub not_shadow_caster: NotShadowCaster,
+    pub not_shadow_receiver: NotShadowReceiver,
+    /// Transform can be used to manipulate the rotation of the skybox.
+    pub transform: Transform,
+    /// Transforms get computed into global transforms used for drawing based on parenting. Note
+    /// that it doesn't make much sense to add a skybox as a child of any other entity; it should
+    /// usually be freestanding.
+    pub global_transform: GlobalTransform,
+}
+
+impl SkyboxBundle {
+    /// Convenience constructor for [`SkyboxBundle`]. Sets the material and uses defaults for
+    /// everything else. In most use cases you should only need to set the material.
+    pub fn new(material: Handle<SkyboxMaterial>) -> Self {
+        Self {
+            material,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a [`SkyboxBundle`] rotated so that the front (-Z) face of the skybox texture is
+    /// toward the given world direction. This is a shortcut for setting the rotation of `transform`
+    /// for the common case of turning the skybox around the vertical axis.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxOrientation};
+    /// # fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+    /// # let skybox_texture = Handle::default();
+    /// commands.spawn_bundle(SkyboxBundle::facing(
+    ///     skyboxes.add(SkyboxMaterial::from_texture(skybox_texture)),
+    ///     SkyboxOrientation::PosX,
+    /// ));
+    /// # }
+    /// ```
+    pub fn facing(material: Handle<SkyboxMaterial>, orientation: SkyboxOrientation) -> Self {
+        Self {
+            material,
+            transform: Transform::from_rotation(orientation.rotation()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Spawns a skybox and a camera which sees only that skybox, both on the given render layer. This
+/// is a starting point for capturing the sky into a texture, e.g. for a cheap planar reflection or
+/// a thumbnail: point the camera at an offscreen render target and the texture will contain just
+/// the sky, since other entities are on other layers.
+///
+/// The skybox shader only depends on the view of the camera drawing it, so skyboxes render the same
+/// way for offscreen cameras as they do for cameras drawing to a window; the sky stays locked to
+/// the capture camera's position regardless of where it is placed.
+///
+/// Returns the skybox entity and the camera entity, in that order.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{spawn_skybox_capture, SkyboxMaterial};
+/// # fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+/// # let skybox_texture = Handle::default();
+/// let (_skybox, _camera) = spawn_skybox_capture(
+///     &mut commands,
+///     skyboxes.add(SkyboxMaterial::from_texture(skybox_texture)),
+///     PerspectiveCameraBundle::default(),
+///     7,
+/// );
+/// # }
+/// ```
+pub fn spawn_skybox_capture(
+    commands: &mut Commands,
+    material: Handle<SkyboxMaterial>,
+    camera: PerspectiveCameraBundle,
+    layer: Layer,
+) -> (Entity, Entity) {
+    let layers = RenderLayers::layer(layer);
+    let skybox = commands
+        .spawn_bundle(SkyboxBundle::new(material))
+        .insert(layers)
+        .id();
+    let camera = commands.spawn_bundle(camera).insert(layers).id();
+    (skybox, camera)
+}
+
+/// Render layer used by [`spawn_reflection_skybox`] for skyboxes which only show up in reflections.
+/// It's the last layer, so it's out of the way of layers counted up from 0 and cameras don't see it
+/// by default.
+pub const SKYBOX_REFLECTION_LAYER: Layer = (RenderLayers::TOTAL_LAYERS - 1) as Layer;
+
+/// Spawns a skybox on [`SKYBOX_REFLECTION_LAYER`], so that it's only drawn by cameras which include
+/// that layer, such as the cameras rendering reflection probes or planar reflections, and not by
+/// the main camera, which only sees layer 0 by default. This decouples the reflected sky from the
+/// visible one, e.g. to reflect a cheap, blurry sky while the main view shows a detailed animated
+/// one.
+///
+/// Give the reflection cameras the layers of the scene plus the reflection layer. If the main
+/// view's skybox is on a layer those cameras see too, such as the default layer 0, both skyboxes
+/// are drawn behind the reflected scene and it's unspecified which one ends up in front, so put it
+/// on a layer of its own:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::view::RenderLayers;
+/// # use bevy_skybox_cubemap::*;
+/// # fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+/// # let (visible_sky, reflected_sky) = (Handle::default(), Handle::default());
+/// // The visible sky is on layer 1, which only the main camera sees.
+/// commands
+///     .spawn_bundle(SkyboxBundle::new(skyboxes.add(SkyboxMaterial::from_texture(visible_sky))))
+///     .insert(RenderLayers::layer(1));
+/// commands
+///     .spawn_bundle(PerspectiveCameraBundle::new_3d())
+///     .insert(RenderLayers::layer(0).with(1));
+///
+/// // The reflected sky is only seen by the reflection camera.
+/// spawn_reflection_skybox(
+///     &mut commands,
+///     skyboxes.add(SkyboxMaterial::from_texture(reflected_sky)),
+/// );
+/// commands
+///     .spawn_bundle(PerspectiveCameraBundle::default())
+///     .insert(RenderLayers::layer(0).with(SKYBOX_REFLECTION_LAYER));
+/// # }
+/// ```
+///
+/// Cameras with the default layers don't see the reflection skybox:
+///
+/// ```
+/// # use bevy::render::view::RenderLayers;
+/// # use bevy_skybox_cubemap::SKYBOX_REFLECTION_LAYER;
+/// let reflection = RenderLayers::layer(SKYBOX_REFLECTION_LAYER);
+/// assert!(!RenderLayers::default().intersects(&reflection));
+/// assert!(RenderLayers::layer(0).with(SKYBOX_REFLECTION_LAYER).intersects(&reflection));
+/// ```
+pub fn spawn_reflection_skybox(
+    commands: &mut Commands,
+    material: Handle<SkyboxMaterial>,
+) -> Entity {
+    commands
+        .spawn_bundle(SkyboxBundle::new(material))
+        .insert(RenderLayers::layer(SKYBOX_REFLECTION_LAYER))
+        .id()
+}
+
+/// Crossfades from the skybox entity `outgoing` to a new skybox with `material`, e.g. for a level
+/// transition, and despawns `outgoing` once the fade is done. Returns the new skybox entity, which
+/// has a [`SkyboxTransition`] component until then.
+///
+/// The new skybox is drawn over the old one, fading from fully transparent to fully opaque over
+/// `fade_seconds`. The fade only starts once the new skybox is ready to be drawn, so its textures
+/// don't pop in halfway through. With a fade of `0.0` or less, the old skybox is replaced as soon
+/// as the new one is ready.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{transition_skybox, SkyboxMaterial};
+/// struct CurrentSky(Entity);
+///
+/// fn enter_night_level(
+///     mut commands: Commands,
+///     mut current: ResMut<CurrentSky>,
+///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+///     asset_server: Res<AssetServer>,
+/// ) {
+///     let night = skyboxes.add(SkyboxMaterial::from_texture(asset_server.load("night.png")));
+///     current.0 = transition_skybox(&mut commands, current.0, night, 2.0);
+/// }
+/// ```
+pub fn transition_skybox(
+    commands: &mut Commands,
+    outgoing: Entity,
+    material: Handle<SkyboxMaterial>,
+    fade_seconds: f32,
+) -> Entity {
+    commands
+        .spawn_bundle(SkyboxBundle::new(material.clone()))
+        .insert(SkyboxTransition {
+            outgoing,
+            fade_seconds,
+            elapsed: 0.0,
+            material,
+            fading: None,
+        })
+        .id()
+}
+
+/// Component on a skybox which is fading in over another one, which is despawned when the fade is
+/// done. See [`transition_skybox`], which adds it.
+///
+/// During the fade the skybox draws a copy of its material with
+/// [`SkyboxBlendMode::Alpha`] and an increasing
+/// [`alpha_scale`](SkyboxMaterial::alpha_scale), so it covers the old skybox, which should be
+/// opaque or on a lower [`SkyboxLayer`]. Once the fade is done, the skybox goes back to its own
+/// material and this component is removed. Changes made to the material during the fade only show
+/// up after it.
+#[derive(Component, Debug, Clone)]
+pub struct SkyboxTransition {
+    /// Skybox entity to despawn once the fade is done.
+    pub outgoing: Entity,
+    /// How long the fade takes, in seconds, from when the skybox is ready to be drawn.
+    pub fade_seconds: f32,
+    elapsed: f32,
+    /// The skybox's own material.
+    material: Handle<SkyboxMaterial>,
+    /// Translucent copy of `material` drawn during the fade, once the material has loaded.
+    fading: Option<Handle<SkyboxMaterial>>,
+}
+
+/// Advances the fades of skyboxes with a [`SkyboxTransition`], and despawns the skyboxes they
+/// replace once they are done.
+fn transition_skyboxes(
+    mut commands: Commands,
+    time: Res<Time>,
+    readiness: Res<SkyboxReadiness>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+    mut skyboxes: Query<(Entity, &mut SkyboxTransition, &mut Handle<SkyboxMaterial>)>,
+) {
+    for (entity, mut transition, mut handle) in skyboxes.iter_mut() {
+        let fading = match &transition.fading {
+            Some(fading) => fading.clone(),
+            None => match materials.get(&transition.material) {
+                Some(material) => {
+                    let fading = materials.add(SkyboxMaterial {
+                        blend_mode: SkyboxBlendMode::Alpha,
+                        alpha_scale: 0.0,
+                        ..material.clone()
+                    });
+                    *handle = fading.clone();
+                    transition.fading = Some(fading.clone());
+                    fading
+                }
+                None => continue,
+            },
+        };
+        if !readiness.is_ready(&fading) {
+            continue;
+        }
+
+        transition.elapsed += time.delta_seconds();
+        // `fade_seconds` of zero, negative, or NaN finishes right away.
+        let t = if transition.fade_seconds > 0.0 {
+            (transition.elapsed / transition.fade_seconds).min(1.0)
+        } else {
+            1.0
+        };
+        if t < 1.0 {
+            let alpha_scale = materials
+                .get(&transition.material)
+                .map_or(1.0, |material| material.alpha_scale);
+            if let Some(material) = materials.get_mut(&fading) {
+                // Smoothstep, so the fade eases in and out like `fade_in_seconds`.
+                material.alpha_scale = alpha_scale * t * t * (3.0 - 2.0 * t);
+            }
+        } else {
+            commands.entity(transition.outgoing).despawn();
+            *handle = transition.material.clone();
+            commands.entity(entity).remove::<SkyboxTransition>();
+        }
+    }
+}
+
+/// Named orientations for a skybox, for use with [`SkyboxBundle::facing`]. Each variant names the
+/// world direction that the front (-Z) face of the skybox texture should be toward. The top and
+/// bottom faces stay on +Y and -Y.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxOrientation {
+    /// Front face toward -Z. This is the default orientation of a skybox, and the direction a
+    /// camera with an unrotated transform looks.
+    NegZ,
+    /// Front face toward +Z, i.e. the skybox is turned all the way around.
+    PosZ,
+    /// Front face toward -X.
+    NegX,
+    /// Front face toward +X.
+    PosX,
+}
+
+impl SkyboxOrientation {
+    /// Gets the rotation to apply to the skybox's transform for this orientation.
+    pub fn rotation(self) -> Quat {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        match self {
+            SkyboxOrientation::NegZ => Quat::IDENTITY,
+            SkyboxOrientation::PosZ => Quat::from_rotation_y(PI),
+            SkyboxOrientation::NegX => Quat::from_rotation_y(FRAC_PI_2),
+            SkyboxOrientation::PosX => Quat::from_rotation_y(-FRAC_PI_2),
+        }
+    }
+}
+
+impl Default for SkyboxOrientation {
+    fn default() -> Self {
+        SkyboxOrientation::NegZ
+    }
+}
+
+impl Default for SkyboxBundle {
+    fn default() -> Self {
+        Self {
+            material: Default::default(),
+            mesh: SKYBOX_MESH_HANDLE.typed(),
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+            no_frustum_culling: NoFrustumCulling,
+            not_shadow_caster: NotShadowCaster,
+            not_shadow_receiver: NotShadowReceiver,
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
+/// Like [`SkyboxBundle`], but for a skybox which Bevy frustum culls like a normal mesh, using
+/// `aabb` as its bounds, instead of always drawing it. This is meant for unusual compositions of
+/// several skyboxes, e.g. a layered sky which should only be drawn while the camera looks toward
+/// part of the world.
+///
+/// Keep in mind that the skybox is always drawn around the camera, wherever its transform is, but
+/// it's culled using `aabb` placed at the skybox's transform like any other mesh. The skybox is
+/// therefore drawn whenever the camera's frustum overlaps `aabb` in the world, and not drawn at all
+/// otherwise, rather than only part of it being drawn. With the bundle's defaults, an `aabb` which
+/// doesn't surround the camera makes the sky disappear when the camera looks away from the origin,
+/// which is exactly what [`SkyboxBundle`] avoids.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::primitives::Aabb;
+/// # use bevy_skybox_cubemap::{CulledSkyboxBundle, SkyboxMaterial};
+/// # fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+/// # let clouds = Handle::default();
+/// // Only draw the cloud layer while the camera can see the valley.
+/// let valley = Aabb::from_min_max(Vec3::new(-50.0, 0.0, -200.0), Vec3::new(50.0, 40.0, -100.0));
+/// commands.spawn_bundle(CulledSkyboxBundle::new(
+///     skyboxes.add(SkyboxMaterial::from_texture(clouds)),
+///     valley,
+/// ));
+/// # }
+/// ```
+#[derive(Bundle)]
+pub struct CulledSkyboxBundle {
+    /// Material to use for the skybox.
+    pub material: Handle<SkyboxMaterial>,
+    /// Mesh to use for the skybox. See [`SkyboxBundle::mesh`].
+    pub mesh: Handle<Mesh>,
+    /// Bounds the skybox is frustum culled with, relative to its transform.
+    pub aabb: Aabb,
+    /// User indication of whether the skybox is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+    pub not_shadow_caster: NotShadowCaster,
+    pub not_shadow_receiver: NotShadowReceiver,
+    /// Transform can be used to manipulate the rotation of the skybox, and places `aabb`.
+    pub transform: Transform,
+    /// Global transform computed from `transform`. See [`SkyboxBundle::global_transform`].
+    pub global_transform: GlobalTransform,
+    /// Marks the skybox as meant to be culled.
+    pub culled: SkyboxCulled,
+}
+
+/// Marker for skyboxes which should be frustum culled like a normal mesh, which
+/// [`CulledSkyboxBundle`] adds. Without it, the [`SkyboxPlugin`] inserts [`NoFrustumCulling`] on
+/// skyboxes spawned without [`SkyboxBundle`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyboxCulled;
+
+impl CulledSkyboxBundle {
+    /// Creates a [`CulledSkyboxBundle`] with the given material and bounds, using defaults for
+    /// everything else.
+    pub fn new(material: Handle<SkyboxMaterial>, aabb: Aabb) -> Self {
+        Self {
+            material,
+            mesh: SKYBOX_MESH_HANDLE.typed(),
+            aabb,
+            visibility: Default::default(),
+            computed_visibility: Default::default(),
+            not_shadow_caster: NotShadowCaster,
+            not_shadow_receiver: NotShadowReceiver,
+            transform: Default::default(),
+            global_transform: Default::default(),
+            culled: SkyboxCulled,
+        }
+    }
+}
+
+/// Component which animates the [`color`](SkyboxMaterial::color) of a skybox's material over a
+/// repeating cycle, e.g. to tint the sky over the course of a day.
+///
+/// Each keyframe is a phase in the range `[0, 1)` and the color the skybox should have at that
+/// point in the cycle. Keyframes must be sorted by phase. Between keyframes the color is linearly
+/// interpolated, and after the last keyframe it interpolates back around to the first one.
+///
+/// The tint is written into the [`SkyboxMaterial`] the entity uses, so every skybox sharing that
+/// material is tinted as well.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial, SkyboxTint};
+/// # fn setup(mut commands: Commands, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+/// # let skybox_texture = Handle::default();
+/// commands
+///     .spawn_bundle(SkyboxBundle::new(
+///         skyboxes.add(SkyboxMaterial::from_texture(skybox_texture)),
+///     ))
+///     .insert(SkyboxTint::new(
+///         vec![
+///             (0.0, Color::WHITE),
+///             (0.4, Color::rgb(1.0, 0.6, 0.4)),
+///             (0.5, Color::rgb(0.1, 0.1, 0.2)),
+///             (0.9, Color::rgb(0.1, 0.1, 0.2)),
+///         ],
+///         120.0,
+///     ));
+/// # }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct SkyboxTint {
+    /// Phase and color pairs, sorted by phase. Phases should be in the range `[0, 1)`.
+    pub keyframes: Vec<(f32, Color)>,
+    /// How long one full cycle through the keyframes takes.
+    pub cycle_seconds: f32,
+}
+
+impl SkyboxTint {
+    /// Creates a new tint animation from a list of keyframes sorted by phase and the length of the
+    /// cycle in seconds.
+    pub fn new(keyframes: Vec<(f32, Color)>, cycle_seconds: f32) -> Self {
+        Self {
+            keyframes,
+            cycle_seconds,
+        }
+    }
+
+    /// Gets the interpolated color at the given phase of the cycle. Returns `None` if there are no
+    /// keyframes.
+    pub fn sample(&self, phase: f32) -> Option<Color> {
+        let phase = phase.rem_euclid(1.0);
+        let next_index = self
+            .keyframes
+            .iter()
+            .position(|(keyframe_phase, _)| *keyframe_phase > phase)
+            .unwrap_or(0);
+        let prev_index = next_index
+            .checked_sub(1)
+            .unwrap_or_else(|| self.keyframes.len().saturating_sub(1));
+        let (prev_phase, prev_color) = *self.keyframes.get(prev_index)?;
+        let (next_phase, next_color) = self.keyframes[next_index];
+
+        let span = (next_phase - prev_phase).rem_euclid(1.0);
+        if span <= 0.0 {
+            return Some(prev_color);
+        }
+        let t = (phase - prev_phase).rem_euclid(1.0) / span;
+        let prev = Vec4::from(prev_color.as_linear_rgba_f32());
+        let next = Vec4::from(next_color.as_linear_rgba_f32());
+        let color = prev.lerp(next, t);
+        Some(Color::rgba_linear(color.x, color.y, color.z, color.w))
+    }
+}
+
+/// System which applies [`SkyboxTint`] animations to skybox materials.
+fn animate_skybox_tints(
+    time: Res<Time>,
+    tints: Query<(&SkyboxTint, &Handle<SkyboxMaterial>)>,
+    mut materials: ResMut<Assets<SkyboxMaterial>>,
+) {
+    let seconds = time.seconds_since_startup();
+    for (tint, handle) in tints.iter() {
+        if tint.cycle_seconds <= 0.0 {
+            continue;
+        }
+        // Wrap while still in `f64`, since an `f32` holding seconds since startup loses too much
+        // precision in long running apps for the cycle to stay smooth.
+        let cycle = tint.cycle_seconds as f64;
+        let phase = ((seconds % cycle) / cycle) as f32;
+        let color = match tint.sample(phase) {
+            Some(color) => color,
+            None => continue,
+        };
+        // Only write to materials which change, since writing prepares them again.
+        if materials
+            .get(handle)
+            .map_or(false, |material| material.color != color)
+        {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color = color;
+            }
+        }
+    }
+}
+
+/// Component describing the texture of a skybox entity's material. The [`SkyboxPlugin`] inserts
+/// this on skybox entities once their texture has loaded and been converted into an array texture,
+/// and keeps it up to date if the texture changes. It is removed again if the material stops having
+/// a usable texture.
+///
+/// This is intended for tooling and debug overlays which want to know about the active skybox
+/// without having to look up the [`Image`] themselves.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkyboxInfo {
+    /// Width and height in pixels of each (square) face of the skybox.
+    pub face_size: u32,
+    /// Number of layers in the array texture. This is 6 for a normal skybox.
+    pub layer_count: u32,
+    /// Format of the skybox texture.
+    pub format: TextureFormat,
+}
+
+impl SkyboxInfo {
+    /// Gets the info for an image, if it is an array texture.
+    fn from_image(image: &Image) -> Option<Self> {
+        let size = image.texture_descriptor.size;
+        if size.depth_or_array_layers <= 1 {
+            return None;
+        }
+        Some(Self {
+            face_size: size.width,
+            layer_count: size.depth_or_array_layers,
+            format: image.texture_descriptor.format,
+        })
+    }
+}
+
+/// System which keeps [`SkyboxInfo`] on skybox entities in sync with their textures.
+fn update_skybox_info(
+    mut commands: Commands,
+    skyboxes: Query<(Entity, &Handle<SkyboxMaterial>, Option<&SkyboxInfo>)>,
+    materials: Res<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+) {
+    for (entity, handle, current) in skyboxes.iter() {
+        let info = materials
+            .get(handle)
+            .and_then(|material| material.texture.as_ref())
+            .and_then(|texture| images.get(texture))
+            .and_then(SkyboxInfo::from_image);
+        match (info, current) {
+            (Some(info), Some(current)) if info == *current => {}
+            (Some(info), _) => {
+                commands.entity(entity).insert(info);
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<SkyboxInfo>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Component which makes a skybox drive Bevy's [`AmbientLight`]. When added to a skybox entity, the
+/// ambient light color is set to the average color of the skybox texture (tinted by the material's
+/// color), and `brightness` is used as the ambient light's brightness. The average is recomputed
+/// whenever the material or its texture changes.
+///
+/// This is opt-in since it overrides whatever ambient light you set yourself. Only add it to one
+/// skybox at a time. Averaging is done on the CPU and supports uncompressed 8-bit RGBA/BGRA and
+/// 32-bit float RGBA textures; color-only skyboxes just use their color.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct SkyboxAmbientLight {
+    /// Brightness of the ambient light.
+    pub brightness: f32,
+}
+
+impl Default for SkyboxAmbientLight {
+    fn default() -> Self {
+        Self { brightness: 0.05 }
+    }
+}
+
+/// System which applies [`SkyboxAmbientLight`].
+fn update_skybox_ambient_light(
+    skyboxes: Query<(
+        &SkyboxAmbientLight,
+        &Handle<SkyboxMaterial>,
+        ChangeTrackers<SkyboxAmbientLight>,
+        ChangeTrackers<Handle<SkyboxMaterial>>,
+    )>,
+    materials: Res<Assets<SkyboxMaterial>>,
+    images: Res<Assets<Image>>,
+    mut material_events: EventReader<AssetEvent<SkyboxMaterial>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    mut ambient_light: ResMut<AmbientLight>,
+) {
+    let changed_materials: HashSet<_> = material_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    let changed_images: HashSet<_> = image_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+
+    for (ambient, handle, ambient_tracker, handle_tracker) in skyboxes.iter() {
+        let material = match materials.get(handle) {
+            Some(material) => material,
+            None => continue,
+        };
+        let texture_changed = material
+            .texture
+            .as_ref()
+            .map_or(false, |texture| changed_images.contains(&texture.id));
+        if !ambient_tracker.is_changed()
+            && !handle_tracker.is_changed()
+            && !changed_materials.contains(&handle.id)
+            && !texture_changed
+        {
+            continue;
+        }
+
+        let average = match &material.texture {
+            Some(texture) => match images.get(texture).and_then(average_color) {
+                Some(average) => average,
+                None => continue,
+            },
+            None => Vec4::ONE,
+        };
+        let color = average * Vec4::from(material.color.as_linear_rgba_f32());
+        ambient_light.color = Color::rgba_linear(color.x, color.y, color.z, 1.0);
+        ambient_light.brightness = ambient.brightness;
+    }
+}
+
+/// Computes the average color of all pixels in an image, as linear RGBA. Returns `None` for
+/// formats which aren't supported by [`linear_texels`].
+fn average_color(image: &Image) -> Option<Vec4> {
+    let texels = linear_texels(image)?;
+    if texels.is_empty() {
+        return None;
+    }
+    Some(texels.iter().sum::<Vec4>() / texels.len() as f32)
+}
+
+/// Decodes every pixel of an image to linear RGBA. Pixels of sRGB textures are decoded to linear,
+/// since averaging or integrating the encoded values gives colors which are too dark. Everything
+/// which derives a color from a skybox texture should go through this so they all agree. Returns
+/// `None` for formats which aren't supported.
+fn linear_texels(image: &Image) -> Option<Vec<Vec4>> {
+    let format = image.texture_descriptor.format;
+    let pixel_size = uncompressed_pixel_size(format).ok()?;
+    image
+        .data
+        .chunks_exact(pixel_size)
+        .map(|pixel| linear_texel(format, pixel))
+        .collect()
+}
+
+/// Decodes a single pixel in the given format to linear RGBA, like [`linear_texels`]. Returns
+/// `None` for formats which aren't supported.
+fn linear_texel(format: TextureFormat, pixel: &[u8]) -> Option<Vec4> {
+    use TextureFormat::*;
+    match format {
+        Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb => {
+            // Alpha is never encoded.
+            let srgb = matches!(format, Rgba8UnormSrgb | Bgra8UnormSrgb);
+            let decode = |value: u8| {
+                let value = value as f32 / 255.0;
+                if srgb {
+                    conversion::srgb_to_linear(value)
+                } else {
+                    value
+                }
+            };
+            let (red, blue) = if matches!(format, Bgra8Unorm | Bgra8UnormSrgb) {
+                (pixel[2], pixel[0])
+            } else {
+                (pixel[0], pixel[2])
+            };
+            Some(Vec4::new(
+                decode(red),
+                decode(pixel[1]),
+                decode(blue),
+                pixel[3] as f32 / 255.0,
+            ))
+        }
+        Rgba32Float => {
+            let channel = |i: usize| {
+                f32::from_le_bytes([
+                    pixel[i * 4],
+                    pixel[i * 4 + 1],
+                    pixel[i * 4 + 2],
+                    pixel[i * 4 + 3],
+                ])
+            };
+            Some(Vec4::new(channel(0), channel(1), channel(2), channel(3)))
+        }
+        _ => None,
+    }
+}
+
+/// Material for a Skybox. Consists of a base color and an optional 6-sided array-texture.
+///
+/// When rendering, the color from the texure is multiplied by the base color. This can be used to
+/// tint the skybox. When creating a new material, the default color is [`Color::WHITE`] which will
+/// have no effect on the texture color.
+///
+/// It is also possible to use a skybox texture with only a [`Color`]. One reason you might want to
+/// do this is that (at time of writing) Bevy does not seem to antialias against the window
+/// [`ClearColor`] properly, instead antialiasing with white for objects that have not other 3d
+/// object behind them. This leads to white borders around antialiased object that overlap the
+/// window clear color. To avoid this, you could spawn a skybox using only a color. Since the skybox
+/// is a 3d rendered object, antialiasing against it works properly.
+///
+/// Skyboxes should generally be spawned using [`SkyboxBundle`], and you can see that type for info
+/// on what components are used with this material.
+///
+/// The material implements [`Reflect`] so inspectors and other tools can edit it at runtime, and
+/// the [`SkyboxPlugin`] registers it with the type registry. Not every field can be reflected with
+/// this version of Bevy, so these have to be set from code:
+///
+/// - The texture handles (`texture`, `overlay_texture`, `low_res_texture`, `lut` and
+///   `mask_texture`). Bevy only reflects an `Option` as a whole, opaque value, which requires the
+///   contents to be serializable, and handles aren't. An inspector would show nothing editable for
+///   them, so they are left out rather than shown as an opaque blob.
+/// - The per-face arrays (`face_tints` and `face_brightness`), since arrays can't be reflected.
+/// - The optional colors (`ground_color` and `sun_color`), which would also only be opaque values.
+/// - `color_space`, `address_mode`, `sampler_override` and `blend_mode`, whose types don't
+///   implement [`Reflect`].
+#[derive(Debug, Clone, TypeUuid, Reflect)]
+// UUID5 generated by first creating a URL-namespaced UUID5 for
+// "https://github.com/google/bevy_skybox_cubemap" (24291f52-ea01-574a-b6ae-3d8182f6086b) then using
+// that as the namespace with `bevy_skybox_cubemap::SkyboxMaterial` as the name.
+#[uuid = "fca7708e-57bb-5a81-977f-95b0e5202de0"]
+pub struct SkyboxMaterial {
+    /// Base color of the skybox. Multiplied with the color from the texture if a texture is
+    /// supplied, otherwise used by itself as the skybox color.
+    ///
+    /// The alpha of the color is multiplied with the texture's alpha too, which makes skyboxes with
+    /// a blended [`blend_mode`](Self::blend_mode) more transparent.
+    pub color: Color,
+    /// Extra factor the alpha of the skybox is multiplied by, on top of the alpha of the texture,
+    /// [`color`](Self::color) and [`face_tints`](Self::face_tints), for skyboxes with a blended
+    /// [`blend_mode`](Self::blend_mode). Defaults to `1.0`, which has no effect. This is handy for
+    /// fading a skybox without touching its colors. It's clamped to `0.0..=1.0`, and has no effect
+    /// on opaque skyboxes.
+    pub alpha_scale: f32,
+    /// Color space in which [`color`](Self::color) is multiplied with the texture. Defaults to
+    /// [`SkyboxColorSpace::Linear`].
+    #[reflect(ignore)]
+    pub color_space: SkyboxColorSpace,
+    /// How much the brightness of [`color`](Self::color) varies with the view direction, from
+    /// `0.0` for a perfectly flat color to `1.0` for a strong gradient. Defaults to `0.0`.
+    ///
+    /// The sky gets lighter toward the top and slightly lighter to one side, which makes a
+    /// color-only skybox look less sterile. Small values, around `0.1`, give a subtle gradient. See
+    /// [`from_color_with_variation`](Self::from_color_with_variation).
+    pub color_variation: f32,
+    /// Texture to use for the skybox. This must be a an aray texture with 6 layers which are all
+    /// square and the same size. See [the crate overview](crate) for details on the required layer
+    /// order and how to get a texture in this format.
+    #[reflect(ignore)]
+    pub texture: Option<Handle<Image>>,
+    /// Whether the texture holds sRGB-encoded color (`true`, the default) or linear color
+    /// (`false`).
+    ///
+    /// If the texture's format has both an sRGB and a linear variant (e.g. `Rgba8Unorm` and
+    /// `Rgba8UnormSrgb`), the [`SkyboxPlugin`] switches the [`Image`] to whichever variant matches
+    /// this setting before it is uploaded, so the GPU decodes it correctly. Formats that only come
+    /// in one variant (such as the float formats typically used for HDR skyboxes) are already
+    /// unambiguous, and this setting has no effect on them.
+    ///
+    /// The default matches how Bevy loads regular image files like PNG and JPEG, so you should only
+    /// need to change this if your skybox looks washed out (set it to `false`) or too dark (set it
+    /// to `true`). Materials sharing the same texture should agree on this setting. It's ignored
+    /// for textures with a format declared with [`SkyboxTextureConversion::expect_format`].
+    pub is_srgb: bool,
+    /// Optional texture drawn over the base texture, such as a starfield or a layer of clouds. This
+    /// must be an array texture in the same format as `texture`, but doesn't need to be the same
+    /// size. The overlay is alpha-blended over the base texture using its own alpha channel, and
+    /// the result is then tinted by `color`. When `None`, the base texture is drawn as-is.
+    #[reflect(ignore)]
+    pub overlay_texture: Option<Handle<Image>>,
+    /// Extra tint applied to each face of the skybox, in the layer order described in the [crate]
+    /// docs (+X, -X, +Y, -Y, +Z, -Z). This is multiplied with `color`, so it can be used to make the
+    /// sky warmer toward one horizon, for example. Like with `color`, the alpha of the tints is
+    /// multiplied in too. Defaults to white for every face, which has no effect.
+    #[reflect(ignore)]
+    pub face_tints: [Color; 6],
+    /// Brightness multiplier for each face of the skybox, in the same order as `face_tints`.
+    /// Defaults to `1.0` for every face, which has no effect.
+    ///
+    /// Unlike the tints, this is a plain intensity which isn't limited to `1.0`, so it's a cheap way
+    /// to hint at a light source in a stylized sky, e.g. by brightening the face toward the sun.
+    /// Negative values are treated as `0.0`.
+    #[reflect(ignore)]
+    pub face_brightness: [f32; 6],
+    /// Tint applied to the upper half of the skybox, above the horizon. Like `face_tints`, this is
+    /// multiplied with `color` and its alpha is ignored. Defaults to white, which has no effect.
+    ///
+    /// Unlike the face tints, the hemisphere tints change smoothly, blending into each other
+    /// across the horizon over [`horizon_blend`](Self::horizon_blend), which makes them an easy way
+    /// to give a sky a stylized look, e.g. a warm sky over a cool ground. The horizon is relative to
+    /// the skybox entity, like for `horizon_darkening`.
+    pub sky_tint: Color,
+    /// Tint applied to the lower half of the skybox, below the horizon. See
+    /// [`sky_tint`](Self::sky_tint). Defaults to white, which has no effect.
+    pub ground_tint: Color,
+    /// Softness of the blend between `sky_tint` and `ground_tint`, as the height above and below
+    /// the horizon of the band they blend over, from `0.0` (a hard edge) to `1.0` (blending over
+    /// the whole sky). Defaults to `0.1`.
+    pub horizon_blend: f32,
+    /// Contrast adjustment of the sky, applied to the linear color of the texture before it's
+    /// tinted. Values above `1.0` push colors away from the `0.5` midpoint for a more dramatic sky,
+    /// and values below `1.0` pull them toward it for a softer one. Defaults to `1.0`, which has no
+    /// effect. Negative values are treated as `0.0`.
+    pub contrast: f32,
+    /// Gamma adjustment of the sky, applied after [`contrast`](Self::contrast) as a power curve:
+    /// each channel is raised to this power. Values above `1.0` darken the midtones and values
+    /// below `1.0` brighten them, while black and white stay put. Defaults to `1.0`, which has no
+    /// effect. It's clamped to `0.01..=10.0`.
+    pub gamma: f32,
+    /// Whether to apply a small amount of dithering to the output color. This hides the banding
+    /// which shows up in smooth gradients and dark, flat-colored skies on 8-bit displays. Off by
+    /// default.
+    pub dither: bool,
+    /// For animated skyboxes, the frame to show. Animated skybox textures have `6 * frames`
+    /// layers, holding the 6 faces of each frame one after another (see
+    /// [`SkyboxTextureConversion::make_array_frames`]). Update this from a system to animate the
+    /// sky. Defaults to 0, which is the only frame of a regular skybox texture.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::SkyboxMaterial;
+    /// const FRAMES: u32 = 8;
+    /// const FRAMES_PER_SECOND: f64 = 4.0;
+    ///
+    /// fn animate_sky(
+    ///     time: Res<Time>,
+    ///     query: Query<&Handle<SkyboxMaterial>>,
+    ///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+    /// ) {
+    ///     let frame = (time.seconds_since_startup() * FRAMES_PER_SECOND) as u32 % FRAMES;
+    ///     for handle in query.iter() {
+    ///         match skyboxes.get(handle) {
+    ///             Some(material) if material.frame != frame => {}
+    ///             _ => continue,
+    ///         }
+    ///         skyboxes.get_mut(handle).unwrap().frame = frame;
+    ///     }
+    /// }
+    /// ```
+    pub frame: u32,
+    /// Optional low resolution version of `texture`, used instead of it when the [`SkyboxQuality`]
+    /// resource is set to [`SkyboxQuality::Low`]. This lets you ship one set of assets which adapts
+    /// to less capable devices. It needs to be converted just like `texture`, and should have the
+    /// same number of layers.
+    #[reflect(ignore)]
+    pub low_res_texture: Option<Handle<Image>>,
+    /// Rotation of the sky, applied in the shader on top of the rotation of the skybox entity's
+    /// transform. Defaults to [`Quat::IDENTITY`].
+    ///
+    /// This rotates the sky the same way as rotating the entity does, but since it's part of the
+    /// material it can be animated by modifying the material, without touching any transforms.
+    pub rotation: Quat,
+    /// Optional 3d texture used as a color grading lookup table. After all the other settings have
+    /// been applied, the red, green, and blue components of the linear output color (clamped to
+    /// `0..=1`) are used as the coordinates to look up the final color in this texture. This allows
+    /// non-linear color grading which `color` and `face_tints` can't do.
+    ///
+    /// The texture should use a linear filtering sampler, otherwise small lookup tables produce
+    /// visible steps. When `None`, the output color is left unchanged.
+    #[reflect(ignore)]
+    pub lut: Option<Handle<Image>>,
+    /// Optional screen-space mask which confines the skybox to part of the view, e.g. to show the
+    /// sky only through a portal or window. When `None`, the default, the skybox covers the whole
+    /// view as usual.
+    ///
+    /// The mask is stretched over the view, and the skybox is only drawn where its red channel is
+    /// at least `0.5`, so render the portal's shape into it in white over black. It's sampled with
+    /// its own sampler, so give it the same aspect ratio as the view to keep the shape undistorted.
+    #[reflect(ignore)]
+    pub mask_texture: Option<Handle<Image>>,
+    /// How much to darken the sky below the horizon, from `0.0` (no darkening, the default) to
+    /// `1.0`. The sky is darkened more the further below the horizon it is, reaching full strength
+    /// straight down. "Down" is relative to the skybox entity, so this follows its transform but
+    /// not [`rotation`](Self::rotation).
+    pub horizon_darkening: f32,
+    /// Whether to mirror the sky above the horizon into the lower half of the skybox, like a
+    /// reflection in still water. Defaults to `false`.
+    ///
+    /// This makes a cheap backdrop for water planes. Like `horizon_darkening`, the horizon is
+    /// relative to the skybox entity; combine the two to make the reflection darker than the sky.
+    pub mirror_below_horizon: bool,
+    /// Solid color drawn for the whole sky below the horizon, instead of the texture, like an
+    /// infinite flat floor. Defaults to `None`, which draws the sky all the way down.
+    ///
+    /// The horizon is relative to the skybox entity. The color isn't multiplied by `color` or the
+    /// tints, and isn't darkened by `horizon_darkening`, but the color grading `lut` and dithering
+    /// still apply.
+    #[reflect(ignore)]
+    pub ground_color: Option<Color>,
+    /// Fraction of each face to crop off at each of its edges, from `0.0` (the default) to just
+    /// under `0.5`. The rest of the face is stretched to cover the whole face.
+    ///
+    /// A small value such as `0.002` hides a pixel or two of seam artifacts or letterboxing baked
+    /// into the edges of the faces. This isn't applied when sampling with
+    /// [`cube_sampling`](Self::cube_sampling), or to the overlay texture.
+    pub face_inset: f32,
+    /// Makes the stars of the [`overlay_texture`](Self::overlay_texture) twinkle, by varying the
+    /// brightness of the overlay over time with a pattern which differs by direction. Defaults to
+    /// `false`.
+    ///
+    /// The time is written into the material's uniform buffer every frame, which is cheap but not
+    /// free, so leave this off for materials without an overlay of stars.
+    pub twinkle: bool,
+    /// Draws reference lines over the sky for debugging its orientation and alignment: latitude and
+    /// longitude lines every 15 degrees, and thicker lines along the edges of the cube's faces. Off
+    /// by default.
+    ///
+    /// Unlike [`SkyboxDebugFaces`], the texture stays visible under the lines, which makes it easy
+    /// to spot a horizon which isn't level, seams which don't line up, or a face which is rotated.
+    /// The lines follow the skybox's rotation, so they stay fixed to the texture.
+    pub debug_grid: bool,
+    /// Color of the [`debug_grid`](Self::debug_grid) lines, blended over the sky using its alpha.
+    /// Defaults to a translucent magenta.
+    pub grid_color: Color,
+    /// Bias added to the mip level the texture is sampled at, to make the sky sharper (negative)
+    /// or softer (positive). Defaults to `0.0`.
+    ///
+    /// This only has an effect for textures with mipmaps, such as those loaded from KTX2 or DDS
+    /// files with their mip chain. It's applied in the shader, since the samplers of this version
+    /// of wgpu have no LOD bias, and isn't applied with
+    /// [`cube_sampling`](Self::cube_sampling), whose cube texture has no mipmaps.
+    pub lod_bias: f32,
+    /// Whether to draw a simple analytic daytime sky instead of `texture` when the material has no
+    /// texture. The sky is lit by a sun in the direction of `sun_direction`, with haziness set by
+    /// `turbidity`. Off by default, in which case materials without a texture are a flat `color`.
+    ///
+    /// The sky is a cheap approximation rather than a physically based model, but it gives a
+    /// dynamic looking sky without any texture. Everything else about the material, such as
+    /// `color`, `face_tints`, and `lut`, applies to it like it would to a texture.
+    pub procedural: bool,
+    /// Direction toward the sun for the procedural sky and the [`sun_color`](Self::sun_color) disc,
+    /// in the skybox's space (before the skybox's transform and [`rotation`](Self::rotation) are
+    /// applied). Doesn't need to be normalized. Defaults to a sun high in the sky.
+    pub sun_direction: Vec3,
+    /// Color of a sun disc drawn over the sky in the direction of `sun_direction`, for skies
+    /// without a sun of their own. Defaults to `None`, which draws no disc.
+    ///
+    /// The disc has a soft edge and is blended over the sky using the color's alpha. Colors
+    /// brighter than 1 make the sun glow with HDR, but each channel is capped at 8 so that it
+    /// doesn't blow out tonemapping. Like [`ground_color`](Self::ground_color), it isn't multiplied
+    /// by `color` or the tints, but the color grading `lut` and dithering still apply. To keep the
+    /// disc in line with the scene's lighting, combine it with [`AlignToLight`].
+    #[reflect(ignore)]
+    pub sun_color: Option<Color>,
+    /// Angular radius of the [`sun_color`](Self::sun_color) disc, in degrees. Defaults to `0.27`,
+    /// about the size of the real sun, and is capped at 20.
+    pub sun_angular_radius: f32,
+    /// Haziness of the procedural sky, from `1.0` for a perfectly clear sky to around `10.0` for a
+    /// very hazy one. Defaults to `2.0`.
+    pub turbidity: f32,
+    /// Whether the shader should encode its output as sRGB itself. Defaults to `false`.
+    ///
+    /// Normal render targets, like the window, use an sRGB format, so the GPU encodes the shader's
+    /// linear output when writing it and this should stay off. When rendering into a target with a
+    /// linear format whose contents will be displayed or saved as if they were sRGB, such as some
+    /// offscreen captures, turn this on to avoid a washed-out sky.
+    pub output_srgb: bool,
+    /// How the texture is sampled past the edges of each face. Defaults to
+    /// [`AddressMode::ClampToEdge`], which uses the texture's own sampler and blends smoothly
+    /// across the seams between faces.
+    ///
+    /// Other modes are for stylized looks: they turn off the blending across seams, so the
+    /// sampler decides what shows up along the edges of each face, e.g. repeating or mirroring the
+    /// face's own texels. Materials with another mode use a sampler with linear filtering and this
+    /// address mode instead of the texture's sampler.
+    #[reflect(ignore)]
+    pub address_mode: AddressMode,
+    /// Sampler to sample the texture with, replacing both the texture's own sampler and the one
+    /// made for [`address_mode`](Self::address_mode). Defaults to `None`, which picks a sampler as
+    /// described there.
+    ///
+    /// This gives full control over every property of the sampler in one place, e.g. anisotropic
+    /// filtering or nearest filtering for a pixelated look. Address modes other than
+    /// [`AddressMode::ClampToEdge`] turn off blending across seams, like `address_mode` does.
+    /// A few properties are adjusted to fit the skybox's bind group: `compare` is ignored, since
+    /// the texture isn't bound for comparisons, and textures which can't be filtered always use
+    /// nearest filtering without anisotropy. The low resolution texture uses the same sampler.
+    #[reflect(ignore)]
+    pub sampler_override: Option<SamplerDescriptor<'static>>,
+    /// How the skybox is composited with what has already been drawn. Defaults to
+    /// [`SkyboxBlendMode::Opaque`].
+    ///
+    /// Use a blended mode to layer several skyboxes in the same view, e.g. an aurora or a ring of
+    /// distant mountains over a base sky. See [`SkyboxBlendMode`] for how layers are ordered.
+    #[reflect(ignore)]
+    pub blend_mode: SkyboxBlendMode,
+    /// How long to fade the skybox in for once it is first ready to be drawn, in seconds. Defaults
+    /// to `0.0`, which shows it right away.
+    ///
+    /// Until its textures are loaded and converted a skybox isn't drawn at all, so the textured
+    /// sky pops in. With a fade, the skybox is first drawn with just its `color` (and tints), and
+    /// eases into the textured sky over this long. The fade only happens once per texture, so
+    /// materials sharing a texture fade in together, and switching back to a texture which has
+    /// already faded in shows it right away.
+    pub fade_in_seconds: f32,
+    /// Whether to sample the texture as a real cubemap with the GPU's own cubemap sampling, which
+    /// filters across the edges between faces with no seams at all. Defaults to `false`, which
+    /// samples the array texture and blends across the seams in the shader instead.
+    ///
+    /// When preparing the material, the faces of the current `frame` are copied into a separate
+    /// cube texture, so this costs one extra copy of the texture in GPU memory, and changing the
+    /// frame copies again. To allow the copy, the [`SkyboxPlugin`] adds
+    /// [`TextureUsages::COPY_SRC`] to the texture. It is ignored for textures which can't be
+    /// filtered, and `address_mode` has no effect on the main texture with it on.
+    pub cube_sampling: bool,
+}
+
+/// How a skybox is composited with what has already been drawn. See
+/// [`SkyboxMaterial::blend_mode`].
+///
+/// Opaque skyboxes are drawn before anything else, so there should be at most one of them in a
+/// view, as the base layer. Blended skyboxes are drawn on top of it, after the scene's opaque
+/// objects and together with its transparent ones, in the order given by their [`SkyboxLayer`]
+/// components, lowest first. Skyboxes never write depth, so layers don't interfere with each other
+/// at the far plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxBlendMode {
+    /// Covers everything behind it, ignoring alpha.
+    Opaque,
+    /// Blends over what's behind it using the alpha of the material's color and textures, scaled
+    /// by [`alpha_scale`](SkyboxMaterial::alpha_scale).
+    Alpha,
+    /// Adds its color, weighted by alpha, to what's behind it. Good for glowing layers like auroras
+    /// or stars.
+    Additive,
+}
+
+impl Default for SkyboxBlendMode {
+    fn default() -> Self {
+        SkyboxBlendMode::Opaque
+    }
+}
+
+/// Color space in which a skybox material's [`color`](SkyboxMaterial::color) is multiplied with its
+/// texture. See [`SkyboxMaterial::color_space`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxColorSpace {
+    /// Multiply in linear space, which is physically based: tinting by 50% gray halves the light
+    /// coming from the sky.
+    Linear,
+    /// Multiply in gamma (sRGB) space, like image editors do, which is often more predictable for
+    /// stylized looks: tinting by 50% gray makes the sky look about half as bright. The sky is
+    /// clamped to the `[0, 1]` range first, so HDR skies lose their highlights.
+    Gamma,
+}
+
+impl Default for SkyboxColorSpace {
+    fn default() -> Self {
+        SkyboxColorSpace::Linear
+    }
+}
+
+/// Draw order of a blended skybox (see [`SkyboxBlendMode`]) relative to the other blended skyboxes
+/// in a view. Lower layers are drawn first, so higher ones end up on top; skyboxes with the same
+/// layer are drawn in the order of their entity ids. Blended skyboxes without this component are
+/// ordered by the position of their entity, like other transparent meshes, which is usually not
+/// what you want.
+///
+/// The order is applied in the render world, by giving layered skyboxes sort distances in each
+/// view's transparent phase which put them behind every other transparent object. Their
+/// transforms are left alone, so this works the same for every camera.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SkyboxLayer(pub i32);
+
+/// Serializable description of a skybox material and how to load its texture, for defining skyboxes
+/// in data files rather than code. Requires the `serde` feature.
+///
+/// Every field has a default, so a definition only needs the fields it changes. In RON, for
+/// example:
+///
+/// ```text
+/// (
+///     texture_path: Some("skyboxes/sunset.png"),
+///     layout: Grid(cols: 3, rows: 2, face_indices: (0, 1, 2, 3, 4, 5)),
+/// )
+/// ```
+///
+/// Deserialize it with the format of your choice, upgrade it with
+/// [`migrate`](SkyboxMaterialDef::migrate) in case it was written by an older version of this
+/// crate, then turn it into a material with [`SkyboxMaterialDef::to_material`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SkyboxMaterialDef {
+    /// Version of the format the definition was written in. Definitions without one predate the
+    /// field, and are version 1.
+    #[serde(default = "first_material_def_version")]
+    pub version: u32,
+    /// The material's [`color`](SkyboxMaterial::color).
+    pub color: Color,
+    /// Path of the texture to load with the [`AssetServer`], or `None` for a color-only skybox.
+    pub texture_path: Option<String>,
+    /// Layout of the faces in the texture.
+    pub layout: SkyboxLayoutDef,
+}
+
+#[cfg(feature = "serde")]
+impl Default for SkyboxMaterialDef {
+    fn default() -> Self {
+        Self {
+            version: SKYBOX_MATERIAL_DEF_VERSION,
+            color: Color::WHITE,
+            texture_path: None,
+            layout: SkyboxLayoutDef::default(),
+        }
+    }
+}
+
+/// Version of the [`SkyboxMaterialDef`] format written by this version of the crate.
+#[cfg(feature = "serde")]
+pub const SKYBOX_MATERIAL_DEF_VERSION: u32 = 1;
+
+#[cfg(feature = "serde")]
+fn first_material_def_version() -> u32 {
+    1
+}
+
+#[cfg(feature = "serde")]
+impl SkyboxMaterialDef {
+    /// Upgrades a definition written by an older version of this crate to the current format,
+    /// filling in anything added since with its default, so data files keep working as the format
+    /// gains settings. Returns an error for definitions written by a newer version of the crate,
+    /// which may rely on settings this version doesn't know about.
+    pub fn migrate(mut self) -> Result<Self, SkyboxError> {
+        if self.version > SKYBOX_MATERIAL_DEF_VERSION {
+            return Err(SkyboxError::UnsupportedDefVersion {
+                version: self.version,
+                supported: SKYBOX_MATERIAL_DEF_VERSION,
+            });
+        }
+        // Version 1 is the current format, so there is nothing to upgrade yet. Upgrades from each
+        // version to the next go here as the format changes.
+        self.version = SKYBOX_MATERIAL_DEF_VERSION;
+        Ok(self)
+    }
+
+    /// Starts loading the texture, queues its conversion according to `layout`, and returns the
+    /// material to add to `Assets<SkyboxMaterial>`.
+    pub fn to_material(
+        &self,
+        asset_server: &AssetServer,
+        conversions: &mut SkyboxTextureConversion,
+    ) -> SkyboxMaterial {
+        let texture = self.texture_path.as_ref().map(|path| {
+            let texture: Handle<Image> = asset_server.load(path.as_str());
+            match self.layout {
+                SkyboxLayoutDef::Array => {}
+                SkyboxLayoutDef::Stacked { frames } => {
+                    conversions.make_array_frames(texture.clone(), frames)
+                }
+                SkyboxLayoutDef::Grid {
+                    cols,
+                    rows,
+                    face_indices,
+                } => conversions.make_array_from_grid(texture.clone(), cols, rows, face_indices),
+            }
+            texture
+        });
+        SkyboxMaterial {
+            color: self.color,
+            texture,
+            ..Default::default()
+        }
+    }
+}
+
+/// Serializable description of the layout of a skybox texture. See [`SkyboxMaterialDef`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SkyboxLayoutDef {
+    /// The texture already has 6 layers, like a cubemap loaded from a KTX2 or DDS file, and isn't
+    /// converted.
+    Array,
+    /// The faces are stacked vertically, as described in the [crate] docs, with `frames` sets of
+    /// faces for animated skyboxes. See [`SkyboxTextureConversion::make_array_frames`].
+    Stacked {
+        /// Number of frames in the texture.
+        frames: u32,
+    },
+    /// The faces are in a regular grid. See [`SkyboxTextureConversion::make_array_from_grid`].
+    Grid {
+        /// Number of columns in the grid.
+        cols: u32,
+        /// Number of rows in the grid.
+        rows: u32,
+        /// Index of the cell holding each face, in layer order.
+        face_indices: [u32; 6],
+    },
+}
+
+#[cfg(feature = "serde")]
+impl Default for SkyboxLayoutDef {
+    fn default() -> Self {
+        SkyboxLayoutDef::Stacked { frames: 1 }
+    }
+}
+
+/// The uniform buffer of a [`SkyboxMaterial`], in the std140 layout of the `CustomMaterial` uniform
+/// in `skybox.frag`, which it must match.
+///
+/// `prepare_asset` fills this in from the material's settings, clamping them to valid ranges.
+/// It's public for render code which works with the skybox shaders directly, e.g. to create a
+/// [`GpuSkyboxMaterial`] with [`GpuSkyboxMaterial::with_uniform`]. The default is a plain white
+/// skybox with every other setting off.
+#[derive(Debug, Clone, AsStd140)]
+pub struct SkyboxUniform {
+    /// Linear color the sky is multiplied with, or a gamma space color if
+    /// [`SKYBOX_FLAGS_GAMMA_COLOR`] is set.
+    pub color: Vec4,
+    /// Linear tint of the +X face. The shader sees the tints as a `vec4[6]` in layer order, which
+    /// has the same std140 layout.
+    pub face_tint_pos_x: Vec4,
+    /// Linear tint of the -X face.
+    pub face_tint_neg_x: Vec4,
+    /// Linear tint of the +Y face.
+    pub face_tint_pos_y: Vec4,
+    /// Linear tint of the -Y face.
+    pub face_tint_neg_y: Vec4,
+    /// Linear tint of the +Z face.
+    pub face_tint_pos_z: Vec4,
+    /// Linear tint of the -Z face.
+    pub face_tint_neg_z: Vec4,
+    /// Bitwise combination of the `SKYBOX_FLAGS_*` constants.
+    pub flags: u32,
+    /// Frame of an animated skybox to show.
+    pub frame: u32,
+    /// First column of the inverse of the skybox's rotation, which is applied to the view
+    /// direction. The shader sees the columns as a `mat3`, which has the same std140 layout.
+    pub rotation_x: Vec4,
+    /// Second column of the inverse rotation.
+    pub rotation_y: Vec4,
+    /// Third column of the inverse rotation.
+    pub rotation_z: Vec4,
+    /// Amount to darken the sky below the horizon, from 0 to 1.
+    pub horizon_darkening: f32,
+    /// Turbidity of the procedural sky, at least 1.
+    pub turbidity: f32,
+    /// Normalized direction of the sun in the procedural sky.
+    pub sun_direction: Vec4,
+    /// How far through the fade from the texture to the low resolution texture the material is.
+    pub lod_blend: f32,
+    /// Brightness of the first four faces, in layer order. The shader sees this and
+    /// `face_brightness_b` as a `vec4[2]`.
+    pub face_brightness_a: Vec4,
+    /// Brightness of the last two faces, in layer order, in `x` and `y`.
+    pub face_brightness_b: Vec4,
+    /// How far the material has faded in, from 0 (only the color) to 1 (fully textured).
+    pub fade: f32,
+    /// Linear color drawn below the horizon, if [`SKYBOX_FLAGS_GROUND`] is set.
+    pub ground_color: Vec4,
+    /// Fraction of each face cropped off at each edge, from 0 to just under 0.5.
+    pub face_inset: f32,
+    /// Seconds since startup, for materials with [`SKYBOX_FLAGS_TWINKLE`] set.
+    pub time: f32,
+    /// Bias added to the mip level of texture samples.
+    pub lod_bias: f32,
+    /// Linear color the sky is mixed toward. See [`SkyboxEnvironment`].
+    pub environment_tint: Vec4,
+    /// How far the sky is mixed toward `environment_tint`, from 0 to 1.
+    pub environment_density: f32,
+    /// Linear color of the sun disc, if [`SKYBOX_FLAGS_SUN_DISC`] is set.
+    pub sun_color: Vec4,
+    /// Angular radius of the sun disc, in radians.
+    pub sun_angular_radius: f32,
+    /// How much the brightness of `color` varies with the view direction, from 0 to 1.
+    pub color_variation: f32,
+    /// Extra factor the alpha of the skybox is multiplied by, from 0 to 1.
+    pub alpha_scale: f32,
+    /// Contrast of the texture around 0.5. 1 has no effect.
+    pub contrast: f32,
+    /// Power the texture's color is raised to, after `contrast`. 1 has no effect.
+    pub gamma: f32,
+    /// Linear color of the debug grid, if [`SKYBOX_FLAGS_GRID`] is set.
+    pub grid_color: Vec4,
+    /// Linear tint of the upper hemisphere.
+    pub sky_tint: Vec4,
+    /// Linear tint of the lower hemisphere.
+    pub ground_tint: Vec4,
+    /// Height above and below the horizon over which the hemisphere tints blend, from 0 to 1.
+    pub horizon_blend: f32,
+}
+
+impl Default for SkyboxUniform {
+    fn default() -> Self {
+        let white = Vec4::ONE;
+        Self {
+            color: white,
+            face_tint_pos_x: white,
+            face_tint_neg_x: white,
+            face_tint_pos_y: white,
+            face_tint_neg_y: white,
+            face_tint_pos_z: white,
+            face_tint_neg_z: white,
+            flags: 0,
+            frame: 0,
+            rotation_x: Vec4::X,
+            rotation_y: Vec4::Y,
+            rotation_z: Vec4::Z,
+            horizon_darkening: 0.0,
+            turbidity: 1.0,
+            sun_direction: Vec4::ZERO,
+            lod_blend: 0.0,
+            face_brightness_a: white,
+            face_brightness_b: white,
+            fade: 1.0,
+            ground_color: Vec4::ZERO,
+            face_inset: 0.0,
+            time: 0.0,
+            lod_bias: 0.0,
+            environment_tint: white,
+            environment_density: 0.0,
+            sun_color: Vec4::ZERO,
+            sun_angular_radius: 0.0,
+            color_variation: 0.0,
+            alpha_scale: 1.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            grid_color: Vec4::ZERO,
+            sky_tint: white,
+            ground_tint: white,
+            horizon_blend: 0.0,
+        }
+    }
+}
+
+// These must match the flag definitions in skybox.frag.
+/// [`SkyboxUniform::flags`] bit which dithers the output. See [`SkyboxMaterial::dither`].
+pub const SKYBOX_FLAGS_DITHER: u32 = 1 << 0;
+/// [`SkyboxUniform::flags`] bit which applies the color grading LUT. See [`SkyboxMaterial::lut`].
+pub const SKYBOX_FLAGS_LUT: u32 = 1 << 1;
+/// [`SkyboxUniform::flags`] bit which draws the procedural sky instead of the texture. See
+/// [`SkyboxMaterial::procedural`].
+pub const SKYBOX_FLAGS_PROCEDURAL: u32 = 1 << 2;
+/// [`SkyboxUniform::flags`] bit which encodes the output as sRGB. See
+/// [`SkyboxMaterial::output_srgb`].
+pub const SKYBOX_FLAGS_OUTPUT_SRGB: u32 = 1 << 3;
+/// [`SkyboxUniform::flags`] bit which turns off blending across the seams between faces.
+pub const SKYBOX_FLAGS_NO_SEAM_BLEND: u32 = 1 << 4;
+/// [`SkyboxUniform::flags`] bit which mirrors the sky below the horizon. See
+/// [`SkyboxMaterial::mirror_below_horizon`].
+pub const SKYBOX_FLAGS_MIRROR: u32 = 1 << 5;
+/// [`SkyboxUniform::flags`] bit which draws the ground color below the horizon. See
+/// [`SkyboxMaterial::ground_color`].
+pub const SKYBOX_FLAGS_GROUND: u32 = 1 << 6;
+/// [`SkyboxUniform::flags`] bit which makes the overlay twinkle. See [`SkyboxMaterial::twinkle`].
+pub const SKYBOX_FLAGS_TWINKLE: u32 = 1 << 7;
+/// [`SkyboxUniform::flags`] bit which draws the sun disc. See [`SkyboxMaterial::sun_color`].
+pub const SKYBOX_FLAGS_SUN_DISC: u32 = 1 << 8;
+/// [`SkyboxUniform::flags`] bit which multiplies the sky by [`SkyboxUniform::color`] in gamma space,
+/// in which case the color is given in gamma space too. See [`SkyboxMaterial::color_space`].
+pub const SKYBOX_FLAGS_GAMMA_COLOR: u32 = 1 << 9;
+/// [`SkyboxUniform::flags`] bit which only draws the skybox where the mask texture allows. See
+/// [`SkyboxMaterial::mask_texture`].
+pub const SKYBOX_FLAGS_MASK: u32 = 1 << 10;
+/// [`SkyboxUniform::flags`] bit which draws the debug grid. See [`SkyboxMaterial::debug_grid`].
+pub const SKYBOX_FLAGS_GRID: u32 = 1 << 11;
+
+/// Largest value of each channel of [`SkyboxMaterial::sun_color`].
+const MAX_SUN_BRIGHTNESS: f32 = 8.0;
+
+/// The render world version of a [`SkyboxMaterial`], holding its uniform buffer and bind group.
+#[derive(Clone)]
+pub struct GpuSkyboxMaterial {
+    buffer: Buffer,
+    /// What was last written to `buffer`, for updating the values which change every frame in
+    /// place. See [`update_skybox_uniforms`].
+    uniform: SkyboxUniform,
+    /// Texture whose fade in [`SkyboxFades`] the material follows, if it fades in.
+    fade_texture: Option<HandleId>,
+    bind_group: BindGroup,
+    key: SkyboxMaterialKey,
+    texture_view: TextureView,
+    sampler: Sampler,
+}
+
+impl GpuSkyboxMaterial {
+    /// Creates a skybox material directly in the render world, for use with your own render
+    /// pipelines. `texture` and `overlay` are the texture views and samplers to bind, e.g. as
+    /// returned by `MeshPipeline::get_image_texture`. `layout` must be the layout created by
+    /// [`SkyboxMaterial`]'s `bind_group_layout`, which is available as the `material_layout` of
+    /// the `MaterialPipeline<SkyboxMaterial>` resource.
+    ///
+    /// The layout always has an overlay texture and a color grading LUT. To leave the overlay out,
+    /// bind the texture behind [`SKYBOX_EMPTY_TEXTURE_HANDLE`], which is fully transparent. The LUT
+    /// is never used by materials created this way, so `lut` can be any 3d texture, such as the
+    /// one behind [`SKYBOX_EMPTY_LUT_HANDLE`].
+    ///
+    /// Settings other than `color` use their defaults: no face tints, no dithering, no rotation, no
+    /// color grading, and the first frame of animated textures.
+    pub fn new(
+        render_device: &RenderDevice,
+        color: Color,
+        texture: (&TextureView, &Sampler),
+        overlay: (&TextureView, &Sampler),
+        lut: (&TextureView, &Sampler),
+        layout: &BindGroupLayout,
+    ) -> Self {
+        let uniform = SkyboxUniform {
+            color: sanitize_color(color, "color"),
+            ..Default::default()
+        };
+        Self::with_uniform(render_device, &uniform, texture, overlay, lut, layout)
+    }
+
+    /// Like [`new`](Self::new), but with all of the settings in `uniform` rather than just the
+    /// color. Flags which need another texture, such as [`SKYBOX_FLAGS_LUT`], use the `overlay`
+    /// and `lut` bindings. There's no low resolution texture, so keep `lod_blend` at 0, and no
+    /// mask texture, so leave [`SKYBOX_FLAGS_MASK`] unset.
+    pub fn with_uniform(
+        render_device: &RenderDevice,
+        uniform: &SkyboxUniform,
+        texture: (&TextureView, &Sampler),
+        overlay: (&TextureView, &Sampler),
+        lut: (&TextureView, &Sampler),
+        layout: &BindGroupLayout,
+    ) -> Self {
+        // The shader skips the mask unless SKYBOX_FLAGS_MASK is set, so its contents don't matter.
+        let mask = render_device
+            .create_texture(&TextureDescriptor {
+                label: Some("skybox_unused_mask"),
+                size: Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsages::TEXTURE_BINDING,
+            })
+            .create_view(&TextureViewDescriptor::default());
+        Self::from_uniform(
+            render_device,
+            uniform,
+            SkyboxTextureBindings {
+                texture,
+                low_res_texture: texture.0,
+                overlay,
+                lut,
+                mask: (&mask, overlay.1),
+            },
+            layout,
+            SkyboxMaterialKey::default(),
+        )
+    }
+
+    /// Gets the bind group to bind for drawing with this material.
+    pub fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+
+    /// Gets the view and sampler of the texture this material draws, for binding the same texture
+    /// in your own render pipelines, e.g. for reflections. This is always the texture the skybox is
+    /// currently drawn with, so it follows changes to the material and its texture.
+    ///
+    /// The view has dimension [`TextureViewDimension::Cube`] for materials with
+    /// [`SkyboxMaterial::cube_sampling`], and [`TextureViewDimension::D2Array`] otherwise, with the
+    /// layers described in the [crate] docs. Color-only materials have a 1x1 white texture.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy::render::render_asset::RenderAssets;
+    /// # use bevy_skybox_cubemap::SkyboxMaterial;
+    /// /// Render world system which binds the skybox texture for reflections.
+    /// fn queue_reflections(
+    ///     skyboxes: Query<&Handle<SkyboxMaterial>>,
+    ///     materials: Res<RenderAssets<SkyboxMaterial>>,
+    /// ) {
+    ///     for skybox in skyboxes.iter() {
+    ///         if let Some(material) = materials.get(skybox) {
+    ///             let (view, sampler) = material.texture();
+    ///             // Create a bind group with `view` and `sampler`...
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn texture(&self) -> (&TextureView, &Sampler) {
+        (&self.texture_view, &self.sampler)
+    }
+
+    /// Creates the uniform buffer and bind group for a material.
+    fn from_uniform(
+        render_device: &RenderDevice,
+        uniform_data: &SkyboxUniform,
+        textures: SkyboxTextureBindings,
+        layout: &BindGroupLayout,
+        key: SkyboxMaterialKey,
+    ) -> Self {
+        let (texture_view, sampler) = textures.texture;
+        let (overlay_view, overlay_sampler) = textures.overlay;
+        let (lut_view, lut_sampler) = textures.lut;
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            contents: uniform_data.as_std140().as_bytes(),
+            label: None,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+        });
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(texture_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(sampler),
+                },
+                BindGroupEntry {
+                    binding: 3,
+                    resource: BindingResource::TextureView(overlay_view),
+                },
+                BindGroupEntry {
+                    binding: 4,
+                    resource: BindingResource::Sampler(overlay_sampler),
+                },
+                BindGroupEntry {
+                    binding: 5,
+                    resource: BindingResource::TextureView(lut_view),
+                },
+                BindGroupEntry {
+                    binding: 6,
+                    resource: BindingResource::Sampler(lut_sampler),
+                },
+                BindGroupEntry {
+                    binding: 7,
+                    resource: BindingResource::TextureView(textures.low_res_texture),
+                },
+                BindGroupEntry {
+                    binding: 8,
+                    resource: BindingResource::TextureView(textures.mask.0),
+                },
+                BindGroupEntry {
+                    binding: 9,
+                    resource: BindingResource::Sampler(textures.mask.1),
+                },
+            ],
+            label: None,
+            layout,
+        });
+        Self {
+            buffer,
+            uniform: uniform_data.clone(),
+            fade_texture: None,
+            bind_group,
+            key,
+            texture_view: texture_view.clone(),
+            sampler: sampler.clone(),
+        }
+    }
+}
+
+/// Textures bound for a skybox material, besides the uniform buffer.
+struct SkyboxTextureBindings<'a> {
+    texture: (&'a TextureView, &'a Sampler),
+    /// Sampled with the sampler of `texture`.
+    low_res_texture: &'a TextureView,
+    overlay: (&'a TextureView, &'a Sampler),
+    lut: (&'a TextureView, &'a Sampler),
+    mask: (&'a TextureView, &'a Sampler),
+}
+
+/// Specialization key for the skybox pipeline. Selects between the variants of the skybox shaders.
+///
+/// Bevy specializes and caches one pipeline per distinct key (together with the mesh's key), so
+/// materials with the same key share a pipeline and only new combinations cause a pipeline to be
+/// compiled. Every field therefore changes what `specialize` produces, and materials are given the
+/// same key whenever they would get the same pipeline. Anything which only changes the bind group
+/// layout, such as a texture which can't be filtered, is covered by the layout itself.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct SkyboxMaterialKey {
+    /// Whether to use the debug fragment shader. See [`SkyboxDebugFaces`].
+    debug_faces: bool,
+    /// Whether to draw the missing texture pattern. See [`SkyboxPlugin::debug_missing_pattern`].
+    missing_pattern: bool,
+    /// Whether to place the skybox at the far plane of a standard depth buffer rather than a
+    /// reverse-Z one. See [`SkyboxPlugin::depth_convention`].
+    standard_depth: bool,
+    /// How the skybox is blended with what's behind it.
+    blend_mode: SkyboxBlendMode,
+    /// Whether the texture is bound as a cube texture. See [`SkyboxMaterial::cube_sampling`].
+    cube: bool,
+    /// The layout from [`SkyboxBindGroupLayouts`] which the material was bound with, if it isn't
+    /// the default material layout. Pipeline specialization has no access to the render world, so
+    /// the layout is passed to it here.
+    alternate_layout: Option<SkyboxLayoutKey>,
+    /// Whether the skybox is drawn into the half resolution texture, which has no depth buffer.
+    /// See [`SkyboxPlugin::half_resolution`].
+    half_res: bool,
+}
+
+/// A bind group layout in a [`SkyboxMaterialKey`], compared and hashed by its id.
+#[derive(Debug, Clone)]
+struct SkyboxLayoutKey(BindGroupLayout);
+
+impl PartialEq for SkyboxLayoutKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.id() == other.0.id()
+    }
+}
+
+impl Eq for SkyboxLayoutKey {}
+
+impl std::hash::Hash for SkyboxLayoutKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.id().hash(state);
+    }
+}
+
+impl SkyboxMaterial {
+    /// Creates a `SkyboxMaterial` with just a texture. The color will be set to [`Color::WHITE`] to
+    /// avoid tinting the texture.
+    pub fn from_texture(texture: Handle<Image>) -> Self {
+        Self {
+            texture: Some(texture),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `SkyboxMaterial` from a texture which already has 6 array layers, such as a cubemap
+    /// loaded from a KTX2 or DDS file. Textures in layered formats are already laid out the way the
+    /// skybox shader expects, so there is no need to pass them to [`SkyboxTextureConversion`].
+    ///
+    /// This is otherwise identical to [`SkyboxMaterial::from_texture`]; the separate constructor
+    /// exists to make it clear that no conversion step is required for these textures.
+    pub fn from_cubemap_texture(texture: Handle<Image>) -> Self {
+        Self::from_texture(texture)
+    }
+
+    /// Generates a skybox texture showing which face is which, and adds it to `images`. It is
+    /// already a 6 layer array texture, so it can be used right away without
+    /// [`SkyboxTextureConversion`]. This is handy for examples and for tracking down orientation
+    /// problems without needing any asset files.
+    ///
+    /// The faces look like the ones drawn with [`SkyboxDebugFaces`]: red for X, green for Y and blue
+    /// for Z, bright for positive faces and dark for negative ones, with one stripe per layer index
+    /// across the middle and a white square in the corner with the face's UV origin. Unlike
+    /// `SkyboxDebugFaces`, this goes through the material's normal texture sampling, so any
+    /// rotation or tinting of the material applies to it.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::{SkyboxBundle, SkyboxMaterial};
+    /// fn setup(
+    ///     mut commands: Commands,
+    ///     mut images: ResMut<Assets<Image>>,
+    ///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+    /// ) {
+    ///     let texture = SkyboxMaterial::debug_texture(&mut images);
+    ///     let skybox = skyboxes.add(SkyboxMaterial::from_texture(texture));
+    ///     commands.spawn_bundle(SkyboxBundle::new(skybox));
+    /// }
+    /// ```
+    pub fn debug_texture(images: &mut Assets<Image>) -> Handle<Image> {
+        const SIZE: usize = 64;
+        let mut data = Vec::with_capacity(SIZE * SIZE * 4 * 6);
+        for face in 0..6 {
+            let mut axis_color = [0u8; 3];
+            // Negative faces are odd layers. Their sRGB value is the same brightness as the 0.35
+            // (linear) used by the debug shader.
+            axis_color[face / 2] = if face % 2 == 0 { 255 } else { 160 };
+            let stripes = (face + 1) as f32;
+            for y in 0..SIZE {
+                for x in 0..SIZE {
+                    let u = (x as f32 + 0.5) / SIZE as f32;
+                    let v = (y as f32 + 0.5) / SIZE as f32;
+                    let stripe = (u * stripes * 2.0 + 0.25).fract() >= 0.5;
+                    let color = if u < 0.15 && v < 0.15 {
+                        [255; 3]
+                    } else if v > 0.4 && v < 0.6 && stripe {
+                        axis_color.map(|channel| 255 - channel)
+                    } else {
+                        axis_color
+                    };
+                    data.extend_from_slice(&[color[0], color[1], color[2], 255]);
+                }
+            }
+        }
+        images.add(Image::new(
+            Extent3d {
+                width: SIZE as u32,
+                height: SIZE as u32,
+                depth_or_array_layers: 6,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        ))
+    }
+
+    /// Replaces the texture of this material, e.g. to change the skybox during a level transition
+    /// without respawning it.
+    ///
+    /// To have the change show up, modify the material through [`Assets::get_mut`]. That marks the
+    /// material as modified, which makes the renderer prepare it again with the new texture. Until
+    /// the new texture is loaded (and converted, if needed) the old GPU material stays in use, so
+    /// the sky doesn't flash while switching.
+    ///
+    /// If the new texture is a stacked image it still has to be converted, just like when creating
+    /// a new material:
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::{SkyboxMaterial, SkyboxTextureConversion};
+    /// fn change_sky(
+    ///     asset_server: Res<AssetServer>,
+    ///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+    ///     mut skybox_conversion: ResMut<SkyboxTextureConversion>,
+    ///     query: Query<&Handle<SkyboxMaterial>>,
+    /// ) {
+    ///     let texture = asset_server.load("night_sky.png");
+    ///     skybox_conversion.make_array(texture.clone());
+    ///     for handle in query.iter() {
+    ///         if let Some(material) = skyboxes.get_mut(handle) {
+    ///             material.set_texture(texture.clone());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn set_texture(&mut self, texture: Handle<Image>) {
+        self.texture = Some(texture);
+    }
+
+    /// Gets the raw bytes of one face of this material's texture, for exporting, hashing, or
+    /// analyzing individual faces. For animated skyboxes, the face is taken from the current
+    /// [`frame`](Self::frame). The bytes are the face's full resolution texels in the texture's
+    /// format, row by row; for block-compressed formats they are rows of blocks. Mipmaps aren't
+    /// included.
+    ///
+    /// Returns `None` if the material has no texture, or the texture isn't loaded or hasn't been
+    /// converted into a skybox array texture yet. See [`sample_skybox`] to read the color in a
+    /// given direction instead.
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::{SkyboxFace, SkyboxMaterial};
+    /// let mut images = Assets::<Image>::default();
+    /// let material = SkyboxMaterial::from_texture(SkyboxMaterial::debug_texture(&mut images));
+    /// let face = material.face_pixels(&images, SkyboxFace::PosY).unwrap();
+    /// // The debug texture is 64x64 RGBA, and the +Y face is green.
+    /// assert_eq!(face.len(), 64 * 64 * 4);
+    /// assert_eq!(face[64 * 4 * 32..][..4], [0, 255, 0, 255]);
+    /// let color_only = SkyboxMaterial::from_color(Color::RED);
+    /// assert!(color_only.face_pixels(&images, SkyboxFace::PosY).is_none());
+    /// ```
+    pub fn face_pixels<'a>(&self, images: &'a Assets<Image>, face: SkyboxFace) -> Option<&'a [u8]> {
+        let image = images.get(self.texture.as_ref()?)?;
+        let descriptor = &image.texture_descriptor;
+        let size = descriptor.size;
+        let layer = (self.frame * 6) as usize + face.layer_index();
+        if size.depth_or_array_layers < 6
+            || layer >= size.depth_or_array_layers as usize
+            || size.width != size.height
+        {
+            return None;
+        }
+        // Each layer is stored with all of its mip levels before the next layer.
+        let layer_len = layer_byte_len(size.width, descriptor.mip_level_count, descriptor.format);
+        let face_len = face_byte_len(size.width, descriptor.format);
+        image
+            .data
+            .get(layer * layer_len..layer * layer_len + face_len)
+    }
+
+    /// Sets [`color`](Self::color) from a hue in degrees and a saturation and value from `0.0` to
+    /// `1.0`, keeping its alpha, and returns the material. See [`set_hsv`](Self::set_hsv).
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::SkyboxMaterial;
+    /// let material = SkyboxMaterial::from_color(Color::WHITE).with_hsv(240.0, 1.0, 1.0);
+    /// let [r, g, b, _] = material.color.as_rgba_f32();
+    /// assert!(r.abs() < 1e-5 && g.abs() < 1e-5 && (b - 1.0).abs() < 1e-5);
+    /// ```
+    pub fn with_hsv(mut self, hue: f32, saturation: f32, value: f32) -> Self {
+        self.set_hsv(hue, saturation, value);
+        self
+    }
+
+    /// Sets [`color`](Self::color) from a hue in degrees and a saturation and value from `0.0` to
+    /// `1.0`, keeping its alpha. Blending towards a color in HSV is often easier to author than in
+    /// RGB, e.g. dimming the sky at night by lowering the value without changing its hue.
+    pub fn set_hsv(&mut self, hue: f32, saturation: f32, value: f32) {
+        let (saturation, value) = (saturation.max(0.0).min(1.0), value.max(0.0).min(1.0));
+        // HSV and HSL share the hue, so only the saturation and brightness need converting.
+        let lightness = value * (1.0 - saturation / 2.0);
+        let hsl_saturation = if lightness <= 0.0 || lightness >= 1.0 {
+            0.0
+        } else {
+            (value - lightness) / lightness.min(1.0 - lightness)
+        };
+        self.color = Color::hsla(
+            hue.rem_euclid(360.0),
+            hsl_saturation,
+            lightness,
+            self.color.a(),
+        )
+        .as_rgba();
+    }
+
+    /// Gets the hue in degrees and the saturation and value from `0.0` to `1.0` of
+    /// [`color`](Self::color). See [`set_hsv`](Self::set_hsv).
+    pub fn hsv(&self) -> (f32, f32, f32) {
+        let (hue, saturation, lightness) = match self.color.as_hsla() {
+            Color::Hsla {
+                hue,
+                saturation,
+                lightness,
+                ..
+            } => (hue, saturation, lightness),
+            _ => unreachable!("as_hsla always returns Color::Hsla"),
+        };
+        let value = lightness + saturation * lightness.min(1.0 - lightness);
+        let hsv_saturation = if value <= 0.0 {
+            0.0
+        } else {
+            2.0 * (1.0 - lightness / value)
+        };
+        (hue, hsv_saturation, value)
+    }
+
+    /// Rotates the hue of [`color`](Self::color) by `degrees`, keeping its saturation, brightness
+    /// and alpha. Calling this a little each frame shifts the tint of the sky through the day.
+    pub fn set_hue_shift(&mut self, degrees: f32) {
+        let (hue, saturation, value) = self.hsv();
+        self.set_hsv(hue + degrees, saturation, value);
+    }
+
+    /// Gets the handle of the skybox texture for use as an environment map, e.g. for reflections
+    /// in your own materials. Returns `None` for color-only skyboxes.
+    ///
+    /// The texture behind this handle is the same 6-layer array texture used to draw the skybox.
+    /// Environment maps are normally sampled through a view with dimension
+    /// [`TextureViewDimension::Cube`] rather than the `D2Array` view Bevy creates for images. Such
+    /// a view can only be created once all 6 layers are square and the same size, which is already
+    /// required of skybox textures; use [`skybox_cube_view`] in the render world to create it. To
+    /// bind exactly the texture the skybox is drawn with, see [`GpuSkyboxMaterial::texture`].
+    ///
+    /// Note that the skybox shader's face orientation is that of a left-handed cube map while
+    /// Bevy's world space is right-handed. When sampling the texture through a cube view, flip the
+    /// Z component of the lookup direction and swap the +Z and -Z layers (layers 4 and 5) to get
+    /// the same result as the skybox.
+    pub fn as_environment_map_handle(&self) -> Option<Handle<Image>> {
+        self.texture.clone()
+    }
+
+    /// Writes each layer of this material's texture to a separate PNG file in `dir`, named
+    /// `layer_0.png`, `layer_1.png` and so on, in the layer order described in the [crate] docs.
+    /// This is meant for debugging textures which end up with their faces scrambled or rotated:
+    /// call it once the texture has been converted to see exactly what the skybox shader samples.
+    ///
+    /// Only textures with 8 bit RGBA or BGRA pixels are supported. Returns an error if the
+    /// material has no texture, the texture isn't loaded, its format isn't supported, or writing
+    /// a file fails.
+    pub fn dump_faces_to_png(
+        &self,
+        images: &Assets<Image>,
+        dir: &std::path::Path,
+    ) -> Result<(), SkyboxError> {
+        let texture = self
+            .texture
+            .as_ref()
+            .and_then(|handle| images.get(handle))
+            .ok_or(SkyboxError::TextureNotLoaded)?;
+        let format = texture.texture_descriptor.format;
+        let swap_red_blue = match format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+            _ => return Err(SkyboxError::UnsupportedFormat(format)),
+        };
+        let size = texture.texture_descriptor.size;
+        // Each layer is stored with all of its mip levels before the next layer, and only the
+        // first (full size) level is written out.
+        let mip_level_count = texture.texture_descriptor.mip_level_count;
+        let layer_len = layer_byte_len(size.width, mip_level_count, format);
+        let face_len = face_byte_len(size.width, format);
+        for (index, layer) in texture.data.chunks_exact(layer_len).enumerate() {
+            let mut pixels = layer[..face_len].to_vec();
+            if swap_red_blue {
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+            }
+            let path = dir.join(format!("layer_{}.png", index));
+            image::save_buffer(
+                &path,
+                &pixels,
+                size.width,
+                size.height,
+                image::ColorType::Rgba8,
+            )
+            .map_err(|err| SkyboxError::WriteFailed {
+                path,
+                message: err.to_string(),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Creates a `SkyboxMaterial` with only a color. This could be used in place of [`ClearColor`]
+    /// if `ClearColor` is giving you issues with antialiasing. Otherwise it's not all that useful.
+    pub fn from_color(color: Color) -> Self {
+        Self {
+            color,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a `SkyboxMaterial` with only a color, like [`from_color`](Self::from_color), but
+    /// with its brightness varying smoothly with the view direction by up to `variation`. See
+    /// [`color_variation`](Self::color_variation).
+    ///
+    /// This makes a nicer background than a perfectly flat color when using a skybox in place of
+    /// [`ClearColor`]:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::SkyboxMaterial;
+    /// let skybox = SkyboxMaterial::from_color_with_variation(Color::rgb(0.2, 0.25, 0.3), 0.1);
+    /// assert_eq!(skybox.color_variation, 0.1);
+    /// assert!(skybox.texture.is_none());
+    /// ```
+    pub fn from_color_with_variation(color: Color, variation: f32) -> Self {
+        Self {
+            color,
+            color_variation: variation,
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for SkyboxMaterial {
+    /// Creates a new skybox material with color set to white and no texture.
+    fn default() -> Self {
+        Self {
+            // Set the default color to white, so when using with a texture the color doesn't impact
+            // the texture color.
+            color: Color::WHITE,
+            alpha_scale: 1.0,
+            color_space: SkyboxColorSpace::Linear,
+            color_variation: 0.0,
+            texture: None,
+            is_srgb: true,
+            overlay_texture: None,
+            face_tints: [Color::WHITE; 6],
+            sky_tint: Color::WHITE,
+            ground_tint: Color::WHITE,
+            horizon_blend: 0.1,
+            face_brightness: [1.0; 6],
+            contrast: 1.0,
+            gamma: 1.0,
+            dither: false,
+            frame: 0,
+            low_res_texture: None,
+            rotation: Quat::IDENTITY,
+            lut: None,
+            mask_texture: None,
+            horizon_darkening: 0.0,
+            mirror_below_horizon: false,
+            ground_color: None,
+            face_inset: 0.0,
+            twinkle: false,
+            debug_grid: false,
+            grid_color: Color::rgba(1.0, 0.0, 1.0, 0.75),
+            lod_bias: 0.0,
+            procedural: false,
+            sun_direction: Vec3::new(0.0, 1.0, -1.0),
+            sun_color: None,
+            sun_angular_radius: 0.27,
+            turbidity: 2.0,
+            output_srgb: false,
+            address_mode: AddressMode::ClampToEdge,
+            sampler_override: None,
+            blend_mode: SkyboxBlendMode::Opaque,
+            fade_in_seconds: 0.0,
+            cube_sampling: false,
+        }
+    }
+}
+
+impl RenderAsset for SkyboxMaterial {
+    type ExtractedAsset = SkyboxMaterial;
+    type PreparedAsset = GpuSkyboxMaterial;
+    type Param = (
+        SRes<RenderDevice>,
+        SRes<RenderQueue>,
+        SRes<MaterialPipeline<Self>>,
+        SRes<RenderAssets<Image>>,
+        Option<SRes<SkyboxDebugFaces>>,
+        Option<SRes<SkyboxLodBlend>>,
+        Option<SRes<SkyboxMissingPattern>>,
+        Option<SRes<SkyboxTextureDescriptors>>,
+        Option<SRes<SkyboxDepthConvention>>,
+        Option<SRes<SkyboxFades>>,
+        Option<SRes<SkyboxTime>>,
+        Option<SRes<SkyboxEnvironment>>,
+        SRes<SkyboxBindGroupLayouts>,
+        SResMut<SkyboxCubeTextures>,
+        SResMut<SkyboxWarnings>,
+    );
+
+    fn extract_asset(&self) -> Self::ExtractedAsset {
+        self.clone()
     }
 
     fn prepare_asset(
         material: Self::ExtractedAsset,
-        (render_device, material_pipeline, gpu_images): &mut SystemParamItem<Self::Param>,
+        (
+            render_device,
+            render_queue,
+            material_pipeline,
+            gpu_images,
+            debug_faces,
+            lod_blend,
+            missing_pattern,
+            texture_descriptors,
+            depth_convention,
+            fades,
+            time,
+            environment,
+            layouts,
+            cube_textures,
+            warnings,
+        ): &mut SystemParamItem<Self::Param>,
     ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
-        let color = Vec4::from_slice(&material.color.as_linear_rgba_f32());
-        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
-            contents: color.as_std140().as_bytes(),
+        // `max` also replaces NaN.
+        let face_brightness = material
+            .face_brightness
+            .map(|brightness| brightness.max(0.0));
+        let face_tints = material
+            .face_tints
+            .map(|tint| sanitize_color(tint, "face tint"));
+        let lod_blend = if material.low_res_texture.is_some() {
+            lod_blend.as_ref().map_or(0.0, |blend| blend.0)
+        } else {
+            0.0
+        };
+        // Materials which fade in start out faded out until `fade_in_skyboxes` has seen them. The
+        // fade is tracked by texture, since that's what the material is waiting for.
+        let fade_texture = match &material.texture {
+            Some(texture) if material.fade_in_seconds > 0.0 => Some(texture.id),
+            _ => None,
+        };
+        let fade = fade_texture.map_or(1.0, |texture| skybox_fade(fades.as_deref(), texture));
+        let inverse_rotation = Mat3::from_quat(material.rotation.normalize().inverse());
+        let mut flags = 0;
+        if material.dither {
+            flags |= SKYBOX_FLAGS_DITHER;
+        }
+        if material.lut.is_some() {
+            flags |= SKYBOX_FLAGS_LUT;
+        }
+        if material.output_srgb {
+            flags |= SKYBOX_FLAGS_OUTPUT_SRGB;
+        }
+        let clamps_to_edge = match &material.sampler_override {
+            Some(sampler) => [sampler.address_mode_u, sampler.address_mode_v]
+                .iter()
+                .all(|&mode| mode == AddressMode::ClampToEdge),
+            None => material.address_mode == AddressMode::ClampToEdge,
+        };
+        if !clamps_to_edge {
+            flags |= SKYBOX_FLAGS_NO_SEAM_BLEND;
+        }
+        if material.mirror_below_horizon {
+            flags |= SKYBOX_FLAGS_MIRROR;
+        }
+        if material.ground_color.is_some() {
+            flags |= SKYBOX_FLAGS_GROUND;
+        }
+        if material.twinkle && material.overlay_texture.is_some() {
+            flags |= SKYBOX_FLAGS_TWINKLE;
+        }
+        if material.sun_color.is_some() {
+            flags |= SKYBOX_FLAGS_SUN_DISC;
+        }
+        if material.color_space == SkyboxColorSpace::Gamma {
+            flags |= SKYBOX_FLAGS_GAMMA_COLOR;
+        }
+        if material.mask_texture.is_some() {
+            flags |= SKYBOX_FLAGS_MASK;
+        }
+        if material.debug_grid {
+            flags |= SKYBOX_FLAGS_GRID;
+        }
+        let procedural = material.procedural && material.texture.is_none();
+        if procedural {
+            flags |= SKYBOX_FLAGS_PROCEDURAL;
+        }
+        let uniform_data = SkyboxUniform {
+            color: match material.color_space {
+                SkyboxColorSpace::Linear => sanitize_color(material.color, "color"),
+                SkyboxColorSpace::Gamma => {
+                    sanitize_vec4(Vec4::from_slice(&material.color.as_rgba_f32()), "color")
+                }
+            },
+            face_tint_pos_x: face_tints[0],
+            face_tint_neg_x: face_tints[1],
+            face_tint_pos_y: face_tints[2],
+            face_tint_neg_y: face_tints[3],
+            face_tint_pos_z: face_tints[4],
+            face_tint_neg_z: face_tints[5],
+            flags,
+            frame: material.frame,
+            rotation_x: inverse_rotation.x_axis.extend(0.0),
+            rotation_y: inverse_rotation.y_axis.extend(0.0),
+            rotation_z: inverse_rotation.z_axis.extend(0.0),
+            // `max` and `min` also replace NaN.
+            horizon_darkening: material.horizon_darkening.max(0.0).min(1.0),
+            turbidity: material.turbidity.max(1.0),
+            sun_direction: material.sun_direction.normalize_or_zero().extend(0.0),
+            lod_blend,
+            face_brightness_a: Vec4::new(
+                face_brightness[0],
+                face_brightness[1],
+                face_brightness[2],
+                face_brightness[3],
+            ),
+            face_brightness_b: Vec4::new(face_brightness[4], face_brightness[5], 0.0, 0.0),
+            fade,
+            ground_color: material
+                .ground_color
+                .map_or(Vec4::ZERO, |ground| sanitize_color(ground, "ground color")),
+            // `max` and `min` also replace NaN. Cropping half of each edge would leave nothing.
+            face_inset: material.face_inset.max(0.0).min(0.499),
+            time: time.as_ref().map_or(0.0, |time| time.0),
+            lod_bias: if material.lod_bias.is_finite() {
+                material.lod_bias
+            } else {
+                0.0
+            },
+            environment_tint: environment.as_ref().map_or(Vec4::ONE, |environment| {
+                sanitize_color(environment.tint, "environment tint")
+            }),
+            // `max` and `min` also replace NaN.
+            environment_density: environment
+                .as_ref()
+                .map_or(0.0, |environment| environment.density.max(0.0).min(1.0)),
+            sun_color: material.sun_color.map_or(Vec4::ZERO, |sun| {
+                let sun = sanitize_color(sun, "sun color");
+                sun.truncate()
+                    .min(Vec3::splat(MAX_SUN_BRIGHTNESS))
+                    .extend(sun.w.min(1.0))
+            }),
+            // `max` and `min` also replace NaN.
+            sun_angular_radius: material.sun_angular_radius.max(0.0).min(20.0).to_radians(),
+            color_variation: material.color_variation.max(0.0).min(1.0),
+            alpha_scale: material.alpha_scale.max(0.0).min(1.0),
+            // `max` and `min` also replace NaN.
+            contrast: material.contrast.max(0.0),
+            gamma: material.gamma.max(0.01).min(10.0),
+            grid_color: sanitize_color(material.grid_color, "grid color"),
+            sky_tint: sanitize_color(material.sky_tint, "sky tint"),
+            ground_tint: sanitize_color(material.ground_tint, "ground tint"),
+            // `max` and `min` also replace NaN.
+            horizon_blend: material.horizon_blend.max(0.0).min(1.0),
+        };
+
+        // Wait for the textures the quality setting needs to be ready. Only one of them is used
+        // unless the material is fading between them, so the other slot gets the same texture.
+        // Missing textures are drawn with the pattern instead, so they are bound like no texture.
+        let missing_texture = material.texture.as_ref().map_or(false, |texture| {
+            missing_pattern
+                .as_ref()
+                .map_or(false, |pattern| pattern.0.contains(&texture.id))
+        });
+        let no_texture = None;
+        let texture = if missing_texture {
+            &no_texture
+        } else if lod_blend >= 1.0 {
+            &material.low_res_texture
+        } else {
+            &material.texture
+        };
+        let low_res_texture = if lod_blend > 0.0 {
+            &material.low_res_texture
+        } else {
+            texture
+        };
+        // Color-only materials bind a white array texture, so they match the layout and the color
+        // comes through unchanged.
+        let white_texture = Some(SKYBOX_WHITE_TEXTURE_HANDLE.typed());
+        let texture = if texture.is_some() {
+            texture
+        } else {
+            &white_texture
+        };
+        let low_res_texture = if low_res_texture.is_some() {
+            low_res_texture
+        } else {
+            &white_texture
+        };
+        // Textures which skipped conversion, e.g. because they were loaded as array textures or
+        // `auto_convert` is off, haven't had their layers checked yet. Binding them anyway would
+        // fail with an opaque wgpu error, so report the problem and keep waiting instead.
+        let max_layers = render_device.limits().max_texture_array_layers;
+        let layer_error = [texture, low_res_texture]
+            .into_iter()
+            .flatten()
+            .find_map(|texture| {
+                let descriptor = texture_descriptors.as_ref()?.0.get(&texture.id)?;
+                check_skybox_layers(descriptor, max_layers)
+                    .err()
+                    .map(|error| (texture.id, error))
+            });
+        if let Some((texture, error)) = layer_error {
+            warnings.error_once(texture, &error);
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        }
+        let (base_color_texture_view, mut base_color_sampler) = if let Some(result) =
+            material_pipeline
+                .mesh_pipeline
+                .get_image_texture(gpu_images, texture)
+        {
+            result
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        };
+        let low_res_texture_view = if let Some((view, _)) = material_pipeline
+            .mesh_pipeline
+            .get_image_texture(gpu_images, low_res_texture)
+        {
+            view
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        };
+
+        let debug_faces = debug_faces.as_ref().map_or(false, |debug| debug.0);
+        // Textures which can't be filtered need the alternate layout and a sampler which doesn't
+        // filter, whatever sampler the image asks for. Materials with a custom address mode need
+        // their own sampler too.
+        let unfilterable = [texture, low_res_texture].into_iter().any(|texture| {
+            texture
+                .as_ref()
+                .and_then(|texture| texture_descriptors.as_ref()?.0.get(&texture.id))
+                .map_or(false, |descriptor| !is_filterable(descriptor.format))
+        });
+        let custom_sampler;
+        if let Some(descriptor) = &material.sampler_override {
+            if descriptor.compare.is_some() {
+                warnings.warn_once("Ignoring the compare function of a skybox's sampler_override.");
+            }
+            let mut descriptor = SamplerDescriptor {
+                compare: None,
+                ..descriptor.clone()
+            };
+            if unfilterable {
+                descriptor.mag_filter = FilterMode::Nearest;
+                descriptor.min_filter = FilterMode::Nearest;
+                descriptor.mipmap_filter = FilterMode::Nearest;
+                descriptor.anisotropy_clamp = None;
+            }
+            validate_skybox_sampler(&mut descriptor, render_device.features(), warnings);
+            custom_sampler = render_device.create_sampler(&descriptor);
+            base_color_sampler = &custom_sampler;
+        } else if unfilterable || material.address_mode != AddressMode::ClampToEdge {
+            let filter = if unfilterable {
+                FilterMode::Nearest
+            } else {
+                FilterMode::Linear
+            };
+            let mut descriptor = SamplerDescriptor {
+                address_mode_u: material.address_mode,
+                address_mode_v: material.address_mode,
+                address_mode_w: material.address_mode,
+                mag_filter: filter,
+                min_filter: filter,
+                mipmap_filter: filter,
+                ..Default::default()
+            };
+            validate_skybox_sampler(&mut descriptor, render_device.features(), warnings);
+            custom_sampler = render_device.create_sampler(&descriptor);
+            base_color_sampler = &custom_sampler;
+        }
+
+        // Materials without an overlay use a fully transparent one so the layout is the same.
+        let overlay_texture = material
+            .overlay_texture
+            .clone()
+            .or_else(|| Some(SKYBOX_EMPTY_TEXTURE_HANDLE.typed()));
+        let (overlay_texture_view, overlay_sampler) = if let Some(result) = material_pipeline
+            .mesh_pipeline
+            .get_image_texture(gpu_images, &overlay_texture)
+        {
+            result
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        };
+
+        // Likewise for materials without a LUT, which the shader skips.
+        let lut = material
+            .lut
+            .clone()
+            .or_else(|| Some(SKYBOX_EMPTY_LUT_HANDLE.typed()));
+        let (lut_view, lut_sampler) = if let Some(result) = material_pipeline
+            .mesh_pipeline
+            .get_image_texture(gpu_images, &lut)
+        {
+            result
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        };
+
+        // And for materials without a mask, which the shader skips too.
+        let mask = material
+            .mask_texture
+            .clone()
+            .or_else(|| Some(SKYBOX_EMPTY_MASK_HANDLE.typed()));
+        let (mask_view, mask_sampler) = if let Some(result) = material_pipeline
+            .mesh_pipeline
+            .get_image_texture(gpu_images, &mask)
+        {
+            result
+        } else {
+            return Err(PrepareAssetError::RetryNextUpdate(material));
+        };
+
+        // Copy the current frame into a cube texture for materials which sample it as a cubemap.
+        let cube_view = if material.cube_sampling && !unfilterable {
+            texture
+                .as_ref()
+                .and_then(|texture| {
+                    Some((
+                        texture.id,
+                        gpu_images.get(texture)?,
+                        texture_descriptors.as_ref()?.0.get(&texture.id)?,
+                    ))
+                })
+                .filter(|(_, _, descriptor)| descriptor.usage.contains(TextureUsages::COPY_SRC))
+                .map(|(texture, gpu_image, descriptor)| {
+                    cube_textures.get_or_copy(
+                        render_device,
+                        render_queue,
+                        texture,
+                        gpu_image,
+                        descriptor,
+                        material.frame,
+                    )
+                })
+        } else {
+            None
+        };
+        let base_color_texture_view = cube_view.as_ref().unwrap_or(base_color_texture_view);
+
+        let alternate_layout = if unfilterable {
+            Some(&layouts.unfilterable)
+        } else if cube_view.is_some() {
+            Some(&layouts.cube)
+        } else {
+            None
+        };
+        let layout = alternate_layout.unwrap_or(&material_pipeline.material_layout);
+        let mut prepared = GpuSkyboxMaterial::from_uniform(
+            render_device,
+            &uniform_data,
+            SkyboxTextureBindings {
+                texture: (base_color_texture_view, base_color_sampler),
+                low_res_texture: low_res_texture_view,
+                overlay: (overlay_texture_view, overlay_sampler),
+                lut: (lut_view, lut_sampler),
+                mask: (mask_view, mask_sampler),
+            },
+            layout,
+            SkyboxMaterialKey {
+                debug_faces,
+                // The debug shader replaces the one with the pattern, so leave it out of the key
+                // to avoid specializing an identical pipeline twice.
+                missing_pattern: !debug_faces && missing_texture,
+                cube: cube_view.is_some(),
+                alternate_layout: alternate_layout.cloned().map(SkyboxLayoutKey),
+                blend_mode: material.blend_mode,
+                standard_depth: depth_convention.as_ref().map_or(false, |convention| {
+                    **convention == SkyboxDepthConvention::Standard
+                }),
+            },
+        );
+        prepared.fade_texture = fade_texture;
+        Ok(prepared)
+    }
+}
+
+/// Render world resource recording which warnings about material settings, and errors about
+/// textures, have been logged. Materials are prepared again whenever they change, so without this
+/// the same warning would be logged over and over.
+#[derive(Default)]
+struct SkyboxWarnings {
+    messages: HashSet<&'static str>,
+    textures: HashSet<HandleId>,
+}
+
+impl SkyboxWarnings {
+    /// Logs `message` as a warning, unless it has been logged before.
+    fn warn_once(&mut self, message: &'static str) {
+        if self.messages.insert(message) {
+            warn!("{}", message);
+        }
+    }
+
+    /// Logs why `texture` can't be used by a skybox, unless an error has been logged for it before.
+    fn error_once(&mut self, texture: HandleId, error: &SkyboxError) {
+        if self.textures.insert(texture) {
+            error!("Can't use Skybox Texture {:?}: {}", texture, error);
+        }
+    }
+}
+
+/// Checks that an array texture has a whole number of 6 layer frames, and no more layers than the
+/// device supports. Single layer textures pass, since they may still be waiting to be converted.
+fn check_skybox_layers(descriptor: &TextureDescriptor, max: u32) -> Result<(), SkyboxError> {
+    let layers = descriptor.size.depth_or_array_layers;
+    if layers > max {
+        Err(SkyboxError::TooManyLayers { layers, max })
+    } else if layers > 1 && layers % 6 != 0 {
+        Err(SkyboxError::WrongLayerCount {
+            layers,
+            expected: (layers / 6).max(1) * 6,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Replaces sampler settings which the device doesn't support, since creating a sampler with them
+/// fails validation. [`AddressMode::ClampToBorder`] needs
+/// [`WgpuFeatures::ADDRESS_MODE_CLAMP_TO_BORDER`], and falls back to [`AddressMode::ClampToEdge`].
+/// Anisotropic filtering needs a power of two clamp up to 16 and linear filtering, and is turned
+/// off otherwise.
+fn validate_skybox_sampler(
+    descriptor: &mut SamplerDescriptor,
+    features: WgpuFeatures,
+    warnings: &mut SkyboxWarnings,
+) {
+    if !features.contains(WgpuFeatures::ADDRESS_MODE_CLAMP_TO_BORDER) {
+        for mode in [
+            &mut descriptor.address_mode_u,
+            &mut descriptor.address_mode_v,
+            &mut descriptor.address_mode_w,
+        ] {
+            if *mode == AddressMode::ClampToBorder {
+                *mode = AddressMode::ClampToEdge;
+                warnings.warn_once(
+                    "Skybox address mode ClampToBorder needs the ADDRESS_MODE_CLAMP_TO_BORDER \
+                     feature, using ClampToEdge instead.",
+                );
+            }
+        }
+    }
+    if let Some(clamp) = descriptor.anisotropy_clamp {
+        let linear = [
+            descriptor.mag_filter,
+            descriptor.min_filter,
+            descriptor.mipmap_filter,
+        ]
+        .into_iter()
+        .all(|filter| filter == FilterMode::Linear);
+        if !clamp.get().is_power_of_two() || clamp.get() > 16 || !linear {
+            descriptor.anisotropy_clamp = None;
+            warnings.warn_once(
+                "Skybox sampler anisotropy_clamp must be 2, 4, 8 or 16, with linear filtering, \
+                 turning anisotropic filtering off.",
+            );
+        }
+    }
+}
+
+/// Converts a color to linear RGBA for the uniform buffer, replacing NaN and infinite components with
+/// 0 and clamping negative components to 0. A single bad component would otherwise produce garbage
+/// across the whole sky. Logs a warning if the color needed fixing.
+fn sanitize_color(color: Color, what: &str) -> Vec4 {
+    sanitize_vec4(Vec4::from_slice(&color.as_linear_rgba_f32()), what)
+}
+
+/// Like [`sanitize_color`], but for a color which has already been converted to floats.
+fn sanitize_vec4(color: Vec4, what: &str) -> Vec4 {
+    let sanitized = Vec4::select(color.cmpge(Vec4::ZERO), color, Vec4::ZERO);
+    // NaN fails every comparison, so it's already been replaced with 0 above.
+    let sanitized = Vec4::select(
+        sanitized.cmplt(Vec4::splat(f32::INFINITY)),
+        sanitized,
+        Vec4::ZERO,
+    );
+    if sanitized != color {
+        warn!(
+            "Skybox material {} {:?} is not a valid color, using {:?} instead",
+            what, color, sanitized,
+        );
+    }
+    sanitized
+}
+
+impl SpecializedMaterial for SkyboxMaterial {
+    type Key = SkyboxMaterialKey;
+
+    fn key(material: &<SkyboxMaterial as RenderAsset>::PreparedAsset) -> Self::Key {
+        material.key.clone()
+    }
+
+    fn specialize(key: Self::Key, descriptor: &mut RenderPipelineDescriptor) {
+        descriptor.vertex.entry_point = "main".into();
+        if key.standard_depth {
+            descriptor
+                .vertex
+                .shader_defs
+                .push("SKYBOX_STANDARD_DEPTH".to_string());
+        }
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.entry_point = "main".into();
+        if key.debug_faces {
+            fragment.shader = SKYBOX_DEBUG_FRAGMENT_SHADER_HANDLE.typed::<Shader>();
+        } else if key.missing_pattern {
+            fragment
+                .shader_defs
+                .push("SKYBOX_MISSING_PATTERN".to_string());
+        }
+        descriptor.primitive.cull_mode = Some(Face::Front);
+
+        // Opaque skyboxes are drawn in the opaque phase (see `alpha_mode`), so they are finished
+        // before any transparent objects are drawn and those always blend over them. Blended
+        // skyboxes with a layer are sorted with the transparent objects by `order_skybox_layers`,
+        // which puts them behind anything else. The skybox never needs to hide anything, so it
+        // also doesn't write depth. Otherwise transparent objects more than a million or so units
+        // away, which are behind the skybox's fixed depth, would fail the depth test against it.
+        // It also leaves the depth buffer holding only the scene's own geometry, which is what
+        // depth-based effects expect. The version of Bevy this crate targets has no depth
+        // prepass; skyboxes should be left out of one if it's added, since there's nothing for
+        // them to contribute to it.
+        //
+        // The depth test is set explicitly too, rather than relying on the material pipeline's
+        // defaults. The skybox sits just in front of the far plane, so anything drawn at a depth
+        // nearer than that covers it, and the comparison includes equality so it isn't lost to
+        // depth precision right at the far plane.
+        if key.half_res {
+            descriptor.depth_stencil = None;
+        } else if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+            depth_stencil.depth_write_enabled = false;
+            depth_stencil.depth_compare = skybox_depth_compare(key.standard_depth);
+        }
+
+        // The material layout is always the second one.
+        if let (Some(layouts), Some(layout)) = (descriptor.layout.as_mut(), &key.alternate_layout) {
+            layouts[1] = layout.0.clone();
+        }
+        if key.cube {
+            descriptor
+                .fragment
+                .as_mut()
+                .unwrap()
+                .shader_defs
+                .push("SKYBOX_CUBE".to_string());
+        }
+        let blend = match key.blend_mode {
+            SkyboxBlendMode::Opaque => None,
+            SkyboxBlendMode::Alpha => Some(BlendState::ALPHA_BLENDING),
+            SkyboxBlendMode::Additive => Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+        };
+        for target in descriptor.fragment.as_mut().unwrap().targets.iter_mut() {
+            target.blend = blend;
+        }
+    }
+
+    fn alpha_mode(material: &<Self as RenderAsset>::PreparedAsset) -> AlphaMode {
+        // Opaque skyboxes are drawn in the opaque phase regardless of the alpha of the material's
+        // color or texture. Blended ones go in the transparent phase, so they are drawn over the
+        // opaque skybox and sorted by `order_skybox_layers`.
+        match material.key.blend_mode {
+            SkyboxBlendMode::Opaque => AlphaMode::Opaque,
+            SkyboxBlendMode::Alpha | SkyboxBlendMode::Additive => AlphaMode::Blend,
+        }
+    }
+
+    fn vertex_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        Some(SKYBOX_VERTEX_SHADER_HANDLE.typed::<Shader>())
+    }
+
+    fn fragment_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        Some(SKYBOX_FRAGMENT_SHADER_HANDLE.typed::<Shader>())
+    }
+
+    fn bind_group(render_asset: &<Self as RenderAsset>::PreparedAsset) -> &BindGroup {
+        &render_asset.bind_group
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        skybox_bind_group_layout(render_device, true, TextureViewDimension::D2Array)
+    }
+}
+
+/// Resource to help with converting skyboxes stored as vertically stacked images as described in
+/// the [crate] documentation into array textures in the correct format for use in a
+/// [`SkyboxMaterial`].
+///
+/// The [`SkyboxPlugin`] will add this resource and install an associated system which handles the
+/// actual texture conversion, unless [`SkyboxPlugin::auto_convert`] is turned off. Conversion is
+/// performed using [`Texture::reinterpret_stacked_2d_as_array`]. If you prefer, you are free to
+/// handle converting textures yourself, or use a texture format + loader which can load array
+/// textures directly.
+///
+/// Conversions which need to rearrange pixel data can be run on the [`AsyncComputeTaskPool`]
+/// using [`make_array_async`](Self::make_array_async), so that large textures don't stall the
+/// frame.
+#[derive(Default)]
+pub struct SkyboxTextureConversion {
+    /// List of textures that should be skyboxes, waiting for them to load. Kept sorted from the
+    /// highest priority to the lowest, and in the order they were queued within each priority.
+    pending: Vec<PendingConversion>,
+    /// Conversions currently running on the async compute pool.
+    tasks: Vec<(Handle<Image>, Task<Result<Image, SkyboxError>>)>,
+    /// Textures which have already been converted, and how and with which priority they were
+    /// converted. If one of these is reloaded (e.g. because it was edited on disk while
+    /// hot-reloading is enabled), it comes back in its original layout and needs to be converted
+    /// again. Otherwise, queueing them again is ignored.
+    converted: HashMap<HandleId, (ConversionMethod, u8)>,
+    /// Formats declared with [`expect_format`](Self::expect_format), by texture.
+    formats: HashMap<HandleId, TextureFormat>,
+    /// Textures taken out of `Assets<Image>` to be converted asynchronously, whose removed events
+    /// should be ignored.
+    taken: HashSet<HandleId>,
+    /// Textures whose asynchronous conversion failed, which are no longer in `Assets<Image>`.
+    failed: HashSet<HandleId>,
+    /// Textures queued since the conversion system last ran, for sending
+    /// [`SkyboxConversionEvent::Queued`].
+    newly_queued: Vec<Handle<Image>>,
+    /// Maximum number of loaded textures to convert (or start converting, for asynchronous
+    /// conversions) each frame, or `None` for no limit, which is the default. The rest wait in the
+    /// queue for the next frame, highest priority first.
+    ///
+    /// Converting a large texture takes a while, so when many textures finish loading at once,
+    /// e.g. when preloading several levels, limiting this spreads the work over several frames
+    /// instead of causing a hitch.
+    pub max_conversions_per_frame: Option<usize>,
+}
+
+/// Function which converts a loaded image into a skybox array texture.
+type ImageConverter = Arc<dyn Fn(Image) -> Result<Image, SkyboxError> + Send + Sync>;
+
+/// How to convert a texture once it has loaded.
+#[derive(Clone)]
+enum ConversionMethod {
+    /// The texture is a vertical stack of this many frames of 6 faces, and is reinterpreted in
+    /// place.
+    Stacked { frames: u32, options: StripOptions },
+    /// The conversion is run on the async compute pool.
+    Async(ImageConverter),
+    /// The texture is a placeholder which is replaced by these separate faces, cropped to
+    /// `regions` and rotated by `rotations`, once they have all loaded. See [`SkyboxAssembler`].
+    Assemble {
+        faces: [Handle<Image>; 6],
+        regions: [Option<Rect<u32>>; 6],
+        rotations: [FaceRotation; 6],
+    },
+}
+
+/// A texture waiting to load so that it can be converted.
+struct PendingConversion {
+    handle: Handle<Image>,
+    method: ConversionMethod,
+    /// Textures with a higher priority are converted first. See
+    /// [`SkyboxTextureConversion::make_array_prioritized`].
+    priority: u8,
+}
+
+impl SkyboxTextureConversion {
+    /// Takes a handle to a texture whose dimensions are `N` wide by `6*N` high, waits for it to load,
+    /// and then reinterprets that texture as an array of 6 textures suitable or a skybox. This is
+    /// useful if your skybox texture is not in a format that has layers. This should only be done
+    /// once per texture, but it's safe to call defensively: queueing a texture which is still
+    /// waiting to be converted logs a warning and does nothing, and queueing one which has already
+    /// been converted is ignored.
+    ///
+    /// Textures whose height isn't exactly 6 times their width aren't converted, and fail with
+    /// [`SkyboxError::WrongAspectRatio`], since their faces wouldn't be square and would look
+    /// stretched on the skybox.
+    ///
+    /// Textures which are already array textures with exactly 6 layers when they finish loading
+    /// (for example cubemaps loaded from KTX2 or DDS files) are left untouched, so it is safe to
+    /// queue those too, but there is no need to; see [`SkyboxMaterial::from_cubemap_texture`].
+    /// Array textures with any other number of layers fail with
+    /// [`SkyboxError::WrongLayerCount`].
+    pub fn make_array(&mut self, handle: Handle<Image>) {
+        self.make_array_frames(handle, 1);
+    }
+
+    /// Like [`make_array`](Self::make_array), but for animated skyboxes with multiple frames. The
+    /// texture must be `N` wide by `6*frames*N` high, with the 6 faces of each frame stacked in
+    /// order, one frame after another. It is converted to an array texture with `6*frames` layers.
+    /// Use [`SkyboxMaterial::frame`] to choose which frame is shown.
+    ///
+    /// Devices limit how many layers an array texture can have (at least 256), so the conversion
+    /// fails with [`SkyboxError::TooManyLayers`], logging an error, if `6*frames` is more than the
+    /// device supports.
+    pub fn make_array_frames(&mut self, handle: Handle<Image>, frames: u32) {
+        self.queue(PendingConversion {
+            handle,
+            method: ConversionMethod::Stacked {
+                frames,
+                options: StripOptions::default(),
+            },
+            priority: 0,
+        });
+    }
+
+    /// Like [`make_array`](Self::make_array), but converts the texture before any queued textures
+    /// with a lower priority. Textures queued with the other methods have priority `0`, and
+    /// textures with the same priority are converted in the order they were queued.
+    ///
+    /// This is useful when queueing many textures at once, e.g. when preloading several levels, to
+    /// have the skybox which is visible right away converted first.
+    pub fn make_array_prioritized(&mut self, handle: Handle<Image>, priority: u8) {
+        self.queue(PendingConversion {
+            handle,
+            method: ConversionMethod::Stacked {
+                frames: 1,
+                options: StripOptions::default(),
+            },
+            priority,
+        });
+    }
+
+    /// Adds a texture to the pending queue, after all textures with the same or higher priority.
+    /// Textures which are already queued are left where they are, with a warning, and textures
+    /// which have already been converted are ignored, since converting them twice would corrupt
+    /// them.
+    fn queue(&mut self, pending: PendingConversion) {
+        if self.converted.contains_key(&pending.handle.id) {
+            info!(
+                "Skybox Texture {:?} has already been converted, ignoring it",
+                pending.handle
+            );
+            return;
+        }
+        if self.is_queued(&pending.handle) {
+            warn!(
+                "Skybox Texture {:?} is already queued for conversion, ignoring it",
+                pending.handle
+            );
+            return;
+        }
+        let index = self
+            .pending
+            .iter()
+            .position(|queued| queued.priority < pending.priority)
+            .unwrap_or(self.pending.len());
+        self.failed.remove(&pending.handle.id);
+        self.newly_queued.push(pending.handle.clone_weak());
+        self.pending.insert(index, pending);
+    }
+
+    /// Like [`make_array`](Self::make_array), but for stacked images exported in a slightly
+    /// different layout than the one described in the [crate] docs, such as with the faces in
+    /// reverse order or with each face upside-down. See [`StripOptions`].
+    ///
+    /// This fixes skyboxes which look correct except for being upside-down or having their faces
+    /// mirrored, without having to re-export them. Flipping faces doesn't work for
+    /// block-compressed textures.
+    pub fn make_array_with_strip_options(&mut self, handle: Handle<Image>, options: StripOptions) {
+        self.queue(PendingConversion {
+            handle,
+            method: ConversionMethod::Stacked { frames: 1, options },
+            priority: 0,
+        });
+    }
+
+    /// Takes a handle to a texture, waits for it to load, and then runs `convert` on it on the
+    /// [`AsyncComputeTaskPool`]. When the conversion finishes, the resulting image replaces the
+    /// original at the same handle. The conversion must return an array texture; anything with a
+    /// single layer fails with [`SkyboxError::WrongLayerCount`].
+    ///
+    /// To avoid copying the image, it's taken out of `Assets<Image>` while it's converted, and the
+    /// [`SkyboxStatus`] of skyboxes using it is [`SkyboxStatus::Converting`]. If the conversion
+    /// fails, or is cancelled with [`cancel`](Self::cancel), the original image is gone: the error
+    /// is logged, and skyboxes using it get [`SkyboxStatus::Failed`].
+    ///
+    /// Use this for conversions which need to move pixel data around, which can take a while for
+    /// large textures. The conversion is run again if the texture is reloaded.
+    pub fn make_array_async<F>(&mut self, handle: Handle<Image>, convert: F)
+    where
+        F: Fn(Image) -> Result<Image, SkyboxError> + Send + Sync + 'static,
+    {
+        self.queue(PendingConversion {
+            handle,
+            method: ConversionMethod::Async(Arc::new(convert)),
+            priority: 0,
+        });
+    }
+
+    /// Like [`make_array`](Self::make_array), but also rotates each face of the skybox by the given
+    /// amount while converting it. The rotations are given in the layer order described in the
+    /// [crate] docs (+X, -X, +Y, -Y, +Z, -Z).
+    ///
+    /// This bakes the rotations into the texture, which is handy for textures exported from a net
+    /// where some faces are oriented differently than the canonical net. The conversion runs on
+    /// the async compute pool, see [`make_array_async`](Self::make_array_async). It doesn't work
+    /// for block-compressed textures.
+    pub fn make_array_with_orientations(
+        &mut self,
+        handle: Handle<Image>,
+        rotations: [FaceRotation; 6],
+    ) {
+        self.make_array_with_layout(handle, Box::new(RotatedFaces(rotations)));
+    }
+
+    /// Like [`make_array`](Self::make_array), but reads the EXIF orientation from `file`, the bytes
+    /// of the JPEG or PNG file the texture is loaded from, and turns the image upright before
+    /// splitting it into faces. Files without an orientation are converted like `make_array` does.
+    ///
+    /// Bevy's image loader throws metadata away, so the file has to be passed in separately, e.g.
+    /// with `include_bytes!` or by reading it from disk. Textures loaded as array textures, such as
+    /// KTX2 and DDS cubemaps, are never converted, so their metadata isn't needed. When the image
+    /// does need turning, the conversion runs on the async compute pool and doesn't work for
+    /// block-compressed textures. See [`OrientedLayout`].
+    pub fn make_array_from_file(&mut self, handle: Handle<Image>, file: &[u8]) {
+        match conversion::exif_orientation(file) {
+            Some(orientation) if (2..=8).contains(&orientation) => {
+                info!(
+                    "Skybox Texture {:?} has EXIF orientation {}, turning it upright",
+                    handle, orientation
+                );
+                self.make_array_with_layout(handle, Box::new(OrientedLayout { orientation }));
+            }
+            _ => self.make_array(handle),
+        }
+    }
+
+    /// Like [`make_array`](Self::make_array), but trims a uniformly colored border off of each face
+    /// while converting it, so assets which ship with padding or a frame around each face don't
+    /// have to be cropped by hand. See [`TrimmedLayout`] for how the border is detected.
+    ///
+    /// The conversion runs on the async compute pool and doesn't work for block-compressed
+    /// textures.
+    pub fn make_array_with_trim(&mut self, handle: Handle<Image>, trim: TrimOptions) {
+        self.make_array_with_layout(handle, Box::new(TrimmedLayout(trim)));
+    }
+
+    /// Takes a handle to an atlas texture with the 6 faces of a skybox packed at arbitrary pixel
+    /// rectangles, waits for it to load, and then copies the faces into an array texture in layer
+    /// order. The rectangles are given in the layer order described in the [crate] docs. See
+    /// [`AtlasLayout`].
+    ///
+    /// The conversion fails, logging an error, if the rectangles aren't all the same size, aren't
+    /// square, or don't fit within the image. It runs on the async compute pool, see
+    /// [`make_array_async`](Self::make_array_async), and doesn't work for block-compressed
+    /// textures.
+    pub fn make_array_from_atlas(&mut self, handle: Handle<Image>, faces: [Rect<u32>; 6]) {
+        self.make_array_with_layout(handle, Box::new(AtlasLayout(faces)));
+    }
+
+    /// Takes a handle to a texture with the 6 faces of a skybox in a regular grid of `cols` by
+    /// `rows` cells, waits for it to load, and then copies the faces into an array texture.
+    /// `face_indices` gives the cell of each face in the layer order described in the [crate] docs,
+    /// counting cells left to right, then top to bottom. See [`GridLayout`].
+    ///
+    /// For example, a 3x2 sheet with +X, -X, +Y in the top row and -Y, +Z, -Z in the bottom row is
+    /// converted with `make_array_from_grid(handle, 3, 2, [0, 1, 2, 3, 4, 5])`. The conversion
+    /// fails, logging an error, if the grid has fewer than 6 cells, an index is outside of it, or
+    /// the image can't be split into the grid evenly. It runs on the async compute pool and
+    /// doesn't work for block-compressed textures.
+    pub fn make_array_from_grid(
+        &mut self,
+        handle: Handle<Image>,
+        cols: u32,
+        rows: u32,
+        face_indices: [u32; 6],
+    ) {
+        self.make_array_with_layout(
+            handle,
+            Box::new(GridLayout {
+                cols,
+                rows,
+                face_indices,
+            }),
+        );
+    }
+
+    /// Takes a handle to a texture whose dimensions are `6N` wide by `N` high, with the faces side
+    /// by side in the layer order described in the [crate] docs from left to right, waits for it to
+    /// load, and then slices it into an array texture. See [`HorizontalStripLayout`] for strips
+    /// with the faces in a different order.
+    ///
+    /// The conversion fails, logging an error, if the width isn't exactly 6 times the height. It
+    /// runs on the async compute pool, and works for block-compressed textures whose faces are a
+    /// whole number of blocks.
+    ///
+    /// ```
+    /// # use bevy::{asset::AssetPlugin, pbr::AmbientLight, render::render_resource::*};
+    /// # use bevy::transform::TransformPlugin;
+    /// # use bevy::prelude::*;
+    /// # use bevy_skybox_cubemap::{SkyboxPlugin, SkyboxTextureConversion};
+    /// # let mut app = App::new();
+    /// # app.add_plugins(MinimalPlugins)
+    /// #     .add_plugin(AssetPlugin)
+    /// #     .add_plugin(TransformPlugin)
+    /// #     .add_asset::<Shader>()
+    /// #     .add_asset::<Mesh>()
+    /// #     .add_asset::<Image>()
+    /// #     .add_asset::<StandardMaterial>()
+    /// #     .init_resource::<AmbientLight>()
+    /// #     .add_plugin(SkyboxPlugin::default());
+    /// // A 12x2 strip of 2x2 faces, where every byte of a face is its column.
+    /// let data = (0..2).flat_map(|_row| (0..6).flat_map(|column| [column; 8])).collect();
+    /// let size = Extent3d { width: 12, height: 2, depth_or_array_layers: 1 };
+    /// let strip = Image::new(size, TextureDimension::D2, data, TextureFormat::Rgba8UnormSrgb);
+    /// let strip = app.world.get_resource_mut::<Assets<Image>>().unwrap().add(strip);
+    /// app.world
+    ///     .get_resource_mut::<SkyboxTextureConversion>()
+    ///     .unwrap()
+    ///     .make_array_from_horizontal_strip(strip.clone());
+    ///
+    /// // The strip is converted in the background over the next few updates.
+    /// let layers = |app: &App| {
+    ///     let images = app.world.get_resource::<Assets<Image>>().unwrap();
+    ///     images.get(&strip).map(|image| image.texture_descriptor.size.depth_or_array_layers)
+    /// };
+    /// for _ in 0..1000 {
+    ///     app.update();
+    ///     if layers(&app) == Some(6) {
+    ///         break;
+    ///     }
+    ///     std::thread::sleep(std::time::Duration::from_millis(1));
+    /// }
+    /// let images = app.world.get_resource::<Assets<Image>>().unwrap();
+    /// let array = images.get(&strip).unwrap();
+    /// assert_eq!(array.texture_descriptor.size.width, 2);
+    /// assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 6);
+    /// let faces: Vec<u8> = array.data.chunks(16).map(|face| face[0]).collect();
+    /// assert_eq!(faces, [0, 1, 2, 3, 4, 5]);
+    /// ```
+    pub fn make_array_from_horizontal_strip(&mut self, handle: Handle<Image>) {
+        self.make_array_with_layout(handle, Box::new(HorizontalStripLayout::default()));
+    }
+
+    /// Takes a handle to a texture, waits for it to load, and then converts it with `layout` on the
+    /// [`AsyncComputeTaskPool`], like [`make_array_async`](Self::make_array_async). Implement
+    /// [`SkyboxLayout`] to support layouts this crate doesn't know about.
+    pub fn make_array_with_layout(&mut self, handle: Handle<Image>, layout: Box<dyn SkyboxLayout>) {
+        self.make_array_async(handle, move |image| layout.to_array(&image));
+    }
+
+    /// Declares the format a queued texture should have once it's converted, which makes its colors
+    /// predictable however the loader decoded the source image: with
+    /// [`TextureFormat::Rgba8Unorm`] the texture is sampled as linear colors, and with
+    /// [`TextureFormat::Rgba8UnormSrgb`] as sRGB. Works with any of the `make_array` methods,
+    /// before or after queueing the texture, as long as it hasn't finished converting yet, and
+    /// also applies to array textures which are otherwise left as they are.
+    ///
+    /// The texture is changed to the format with [`convert_skybox_format`] at the end of its
+    /// conversion. If that isn't possible, the conversion fails with
+    /// [`SkyboxError::FormatNotConvertible`]. A declared format overrides
+    /// [`SkyboxMaterial::is_srgb`] for the texture, which otherwise switches its color space to
+    /// match the material.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy::render::render_resource::TextureFormat;
+    /// # use bevy_skybox_cubemap::SkyboxTextureConversion;
+    /// # fn setup(asset_server: Res<AssetServer>, mut conversion: ResMut<SkyboxTextureConversion>) {
+    /// // A sky whose pixels store linear data, whatever the loader thinks.
+    /// let texture: Handle<Image> = asset_server.load("linear_sky.png");
+    /// conversion.make_array(texture.clone());
+    /// conversion.expect_format(&texture, TextureFormat::Rgba8Unorm);
+    /// # }
+    /// ```
+    pub fn expect_format(&mut self, handle: &Handle<Image>, format: TextureFormat) {
+        self.formats.insert(handle.id, format);
+    }
+
+    /// Gets the format declared for a texture with [`expect_format`](Self::expect_format), if any.
+    pub fn expected_format(&self, handle: &Handle<Image>) -> Option<TextureFormat> {
+        self.formats.get(&handle.id).copied()
+    }
+
+    /// Removes a texture from the queue of textures waiting to be converted. Returns `true` if the
+    /// texture was still waiting for conversion, or `false` if it was never queued or has already
+    /// been converted. Conversions already running on the async compute pool are cancelled too.
+    ///
+    /// This is useful if you queue a skybox texture and then tear down whatever was going to use
+    /// it before it finishes loading, e.g. during a scene transition.
+    pub fn cancel(&mut self, handle: &Handle<Image>) -> bool {
+        // A texture cancelled in the frame it was queued never gets its `Queued` event.
+        self.newly_queued.retain(|queued| queued != handle);
+        if let Some(index) = self
+            .pending
+            .iter()
+            .position(|queued| queued.handle == *handle)
+        {
+            self.pending.remove(index);
+            return true;
+        }
+        // Dropping a task cancels it.
+        match self.tasks.iter().position(|(queued, _)| queued == handle) {
+            Some(index) => {
+                self.tasks.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every texture from the queue, cancelling conversions running on the async compute
+    /// pool, and forgets which textures have been converted and the formats declared for them.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.newly_queued.clear();
+        self.tasks.clear();
+        self.converted.clear();
+        self.formats.clear();
+        self.failed.clear();
+    }
+
+    /// Checks whether a texture is queued or currently being converted.
+    fn is_queued(&self, handle: &Handle<Image>) -> bool {
+        self.pending.iter().any(|queued| queued.handle == *handle)
+            || self.tasks.iter().any(|(queued, _)| queued == handle)
+    }
+
+    /// Checks whether a texture was lost because its asynchronous conversion failed.
+    fn has_failed(&self, handle: &Handle<Image>) -> bool {
+        self.failed.contains(&handle.id)
+    }
+}
+
+/// Reinterprets an `N x 6N` image containing the six faces of a skybox stacked vertically as a 6
+/// layer array texture. This is the conversion [`SkyboxTextureConversion::make_array`] applies once
+/// a texture has loaded, exposed so it can be used directly on an [`Image`], e.g. in asset
+/// pipelines or tests that don't run a full app.
+///
+/// Images which already have multiple array layers are left unchanged. Returns an error (also
+/// leaving the image unchanged) if the image can't be split into 6 faces, or if the faces wouldn't
+/// be square: the height of the image must be exactly 6 times its width. Non-square faces would be
+/// stretched across the skybox.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+/// # use bevy_skybox_cubemap::reinterpret_stacked_skybox;
+/// let mut image = Image::new_fill(
+///     Extent3d { width: 4, height: 24, depth_or_array_layers: 1 },
+///     TextureDimension::D2,
+///     &[255, 0, 0, 255],
+///     TextureFormat::Rgba8UnormSrgb,
+/// );
+/// reinterpret_stacked_skybox(&mut image).unwrap();
+/// assert_eq!(image.texture_descriptor.size.height, 4);
+/// assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 6);
+/// ```
+pub fn reinterpret_stacked_skybox(image: &mut Image) -> Result<(), SkyboxError> {
+    reinterpret_stacked_skybox_frames(image, 1)
+}
+
+/// Like [`reinterpret_stacked_skybox`], but for an animated skybox with `frames` sets of 6 faces
+/// stacked one after another. The result is an array texture with `6 * frames` layers.
+///
+/// Block-compressed images can be reinterpreted as long as the faces are a whole number of
+/// blocks in size. Compressed images which already have their layers, like a BC7 cubemap loaded
+/// from a KTX2 file, are left unchanged:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureFormat};
+/// # use bevy_skybox_cubemap::{normalize_skybox_format, reinterpret_stacked_skybox_frames};
+/// let mut image = Image::default();
+/// image.texture_descriptor.format = TextureFormat::Bc7RgbaUnormSrgb;
+/// image.texture_descriptor.size = Extent3d { width: 4, height: 4, depth_or_array_layers: 6 };
+/// // One 16 byte block per face.
+/// image.data = vec![0; 16 * 6];
+/// reinterpret_stacked_skybox_frames(&mut image, 1).unwrap();
+/// assert!(!normalize_skybox_format(&mut image));
+/// assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 6);
+/// assert_eq!(image.texture_descriptor.format, TextureFormat::Bc7RgbaUnormSrgb);
+/// ```
+pub fn reinterpret_stacked_skybox_frames(
+    image: &mut Image,
+    frames: u32,
+) -> Result<(), SkyboxError> {
+    let size = image.texture_descriptor.size;
+    if size.depth_or_array_layers > 1 {
+        return Ok(());
+    }
+    if size.height % 6 != 0 {
+        return Err(SkyboxError::NotDivisibleBySix {
+            height: size.height,
+        });
+    }
+    let layers = frames.max(1) * 6;
+    if size.height % layers != 0 {
+        return Err(SkyboxError::NotDivisibleIntoLayers {
+            height: size.height,
+            layers,
+        });
+    }
+    let face_size = (size.width, size.height / layers);
+    if face_size.0 != face_size.1 {
+        return Err(SkyboxError::WrongAspectRatio {
+            image_size: (size.width, size.height),
+            expected: (1, layers),
+        });
+    }
+    // Block-compressed data is split by whole rows of blocks, so each face needs to start on a
+    // block boundary.
+    let (block_width, block_height) = image.texture_descriptor.format.describe().block_dimensions;
+    if face_size.0 % block_width as u32 != 0 || face_size.1 % block_height as u32 != 0 {
+        return Err(SkyboxError::FaceNotBlockAligned {
+            face_size,
+            block_size: (block_width as u32, block_height as u32),
+        });
+    }
+    image.reinterpret_stacked_2d_as_array(layers);
+    Ok(())
+}
+
+/// Direction in which the 6 faces of a strip image follow one another. See
+/// [`reinterpret_skybox_strip`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitAxis {
+    /// Faces stacked top to bottom in an `N x 6N` image, as described in the [crate] docs.
+    Vertical,
+    /// Faces side by side from left to right in a `6N x N` image.
+    Horizontal,
+}
+
+impl Default for SplitAxis {
+    fn default() -> Self {
+        SplitAxis::Vertical
+    }
+}
+
+/// Like [`reinterpret_stacked_skybox`], but for a strip of faces laid out along `axis`, in the
+/// layer order described in the [crate] docs. Vertical strips are reinterpreted in place without
+/// copying. Horizontal strips are first rearranged into a vertical strip on the CPU, which takes a
+/// single pass over the data and works for block-compressed formats too, as long as each face is a
+/// whole number of blocks.
+///
+/// Images which already have multiple array layers are left unchanged. Returns an error, leaving
+/// the image unchanged, if the image can't be split into 6 square faces along `axis`.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+/// # use bevy_skybox_cubemap::{reinterpret_skybox_strip, SplitAxis};
+/// // A 6x1 strip where each face is a single pixel whose red channel is its column.
+/// let data = (0..6).flat_map(|column| [column, 0, 0, 255]).collect();
+/// let size = Extent3d { width: 6, height: 1, depth_or_array_layers: 1 };
+/// let mut image = Image::new(size, TextureDimension::D2, data, TextureFormat::Rgba8UnormSrgb);
+///
+/// reinterpret_skybox_strip(&mut image, SplitAxis::Horizontal).unwrap();
+/// assert_eq!(image.texture_descriptor.size.width, 1);
+/// assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 6);
+/// let faces: Vec<u8> = image.data.chunks(4).map(|pixel| pixel[0]).collect();
+/// assert_eq!(faces, [0, 1, 2, 3, 4, 5]);
+/// ```
+pub fn reinterpret_skybox_strip(image: &mut Image, axis: SplitAxis) -> Result<(), SkyboxError> {
+    let size = image.texture_descriptor.size;
+    if axis == SplitAxis::Vertical || size.depth_or_array_layers > 1 {
+        return reinterpret_stacked_skybox(image);
+    }
+    if size.width % 6 != 0 {
+        return Err(SkyboxError::NotDivisibleIntoGrid {
+            image_size: (size.width, size.height),
+            cols: 6,
+            rows: 1,
+        });
+    }
+    let face_size = (size.width / 6, size.height);
+    if face_size.0 != face_size.1 {
+        return Err(SkyboxError::WrongAspectRatio {
+            image_size: (size.width, size.height),
+            expected: (6, 1),
+        });
+    }
+    let format = image.texture_descriptor.format;
+    let (block_width, block_height) = format.describe().block_dimensions;
+    if face_size.0 % block_width as u32 != 0 || face_size.1 % block_height as u32 != 0 {
+        return Err(SkyboxError::FaceNotBlockAligned {
+            face_size,
+            block_size: (block_width as u32, block_height as u32),
+        });
+    }
+    let face_len = face_byte_len(face_size.0, format);
+    let block_rows = (face_size.1 / block_height as u32) as usize;
+    image.data = conversion::horizontal_to_stacked(&image.data, face_len / block_rows, block_rows);
+    image.texture_descriptor.size = Extent3d {
+        width: face_size.0,
+        height: face_size.1 * 6,
+        depth_or_array_layers: 1,
+    };
+    reinterpret_stacked_skybox(image)
+}
+
+/// A layout of skybox faces within an image, which knows how to convert images in that layout into
+/// skybox array textures. Used with [`SkyboxTextureConversion::make_array_with_layout`].
+///
+/// The crate's own layouts are [`StackedLayout`], [`RotatedFaces`], [`OrientedLayout`],
+/// [`TrimmedLayout`], [`AtlasLayout`], [`GridLayout`], [`HorizontalStripLayout`] and
+/// [`NetLayout`]. Implement this trait for other layouts, such as faces with borders between them,
+/// to convert them with the same machinery:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxError, SkyboxLayout, StackedLayout};
+/// /// Faces stacked vertically, with a one pixel border above each face.
+/// struct BorderedStack;
+///
+/// impl SkyboxLayout for BorderedStack {
+///     fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+///         let stacked = image.clone();
+///         // ... strip the borders from `stacked` ...
+///         StackedLayout::default().to_array(&stacked)
+///     }
+/// }
+/// ```
+pub trait SkyboxLayout: Send + Sync {
+    /// Converts an image in this layout into an array texture with the faces of the skybox as its
+    /// layers, in the order described in the [crate] docs.
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError>;
+}
+
+/// Layout of `frames` sets of 6 faces stacked vertically, as described in the [crate] docs. This is
+/// the layout handled by [`SkyboxTextureConversion::make_array`] and its variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StackedLayout {
+    /// Number of frames of an animated skybox in the image.
+    pub frames: u32,
+    /// Differences from the documented layout to fix up while converting.
+    pub strip: StripOptions,
+}
+
+impl Default for StackedLayout {
+    fn default() -> Self {
+        Self {
+            frames: 1,
+            strip: StripOptions::default(),
+        }
+    }
+}
+
+impl SkyboxLayout for StackedLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        let mut image = image.clone();
+        rearrange_stacked_faces(&mut image, self.frames, self.strip)?;
+        reinterpret_stacked_skybox_frames(&mut image, self.frames)?;
+        Ok(image)
+    }
+}
+
+/// Layout of 6 faces stacked vertically, where each face needs rotating by the corresponding
+/// rotation. See [`SkyboxTextureConversion::make_array_with_orientations`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RotatedFaces(pub [FaceRotation; 6]);
+
+impl SkyboxLayout for RotatedFaces {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        rotate_stacked_skybox_faces(image.clone(), self.0)
+    }
+}
+
+/// Layout of 6 faces stacked vertically, as described in the [crate] docs, in an image which is
+/// stored rotated or mirrored as described by its EXIF `orientation`. The image is turned upright
+/// before it's split into faces. See [`SkyboxTextureConversion::make_array_from_file`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrientedLayout {
+    /// EXIF orientation of the image, from 1 to 8. See [`conversion::exif_orientation`].
+    pub orientation: u16,
+}
+
+impl SkyboxLayout for OrientedLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        let size = image.texture_descriptor.size;
+        let pixel_size = uncompressed_pixel_size(image.texture_descriptor.format)?;
+        let (data, width, height) = conversion::orient_image(
+            &image.data,
+            size.width as usize,
+            size.height as usize,
+            pixel_size,
+            self.orientation,
+        );
+        let mut upright = Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            image.texture_descriptor.format,
+        );
+        upright.sampler_descriptor = image.sampler_descriptor.clone();
+        StackedLayout::default().to_array(&upright)
+    }
+}
+
+/// How to trim a uniformly colored border, such as padding or a decorative frame, off of each face
+/// while converting a skybox. See [`SkyboxTextureConversion::make_array_with_trim`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TrimOptions {
+    /// Color of the border. If `None`, the border of each face is the color of its top left pixel.
+    ///
+    /// Setting a color only works for 8 bit RGBA and BGRA textures; the color is compared with the
+    /// sRGB or linear values, matching the texture's format.
+    pub border_color: Option<Color>,
+    /// The thickest border, in pixels, to trim off of each edge of each face.
+    pub max_thickness: u32,
+}
+
+/// Layout of 6 faces stacked vertically, as described in the [crate] docs, where each face has a
+/// uniformly colored border which is trimmed off. See [`TrimOptions`].
+///
+/// The same thickness is trimmed off of every edge of every face, so the faces stay square and the
+/// same size: the thinnest border found on any of them. Pixels of 8 bit formats match the border
+/// if each channel is within a few steps of it, which allows for compression artifacts. If any
+/// face has no border, nothing is trimmed. The faces are cropped rather than stretched, so the
+/// resulting array texture is smaller than the original faces by twice the trimmed thickness.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TrimmedLayout(pub TrimOptions);
+
+impl SkyboxLayout for TrimmedLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        use TextureFormat::*;
+        let mut array = StackedLayout::default().to_array(image)?;
+        let format = array.texture_descriptor.format;
+        let pixel_size = uncompressed_pixel_size(format)?;
+        let face_size = array.texture_descriptor.size;
+        if face_size.width != face_size.height {
+            return Err(SkyboxError::FaceNotSquare {
+                width: face_size.width,
+                height: face_size.height,
+            });
+        }
+        let size = face_size.width as usize;
+        let face_len = size * size * pixel_size;
+
+        let eight_bit = matches!(
+            format,
+            Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb
+        );
+        let border_color = match self.0.border_color {
+            Some(color) if eight_bit => {
+                let [r, g, b, a] = if format.describe().srgb {
+                    color.as_rgba_f32()
+                } else {
+                    color.as_linear_rgba_f32()
+                };
+                let [r, g, b, a] =
+                    [r, g, b, a].map(|c| (c.max(0.0).min(1.0) * 255.0).round() as u8);
+                Some(match format {
+                    Bgra8Unorm | Bgra8UnormSrgb => [b, g, r, a],
+                    _ => [r, g, b, a],
+                })
+            }
+            Some(_) => return Err(SkyboxError::UnsupportedFormat(format)),
+            None => None,
+        };
+        let tolerance = if eight_bit { 4 } else { 0 };
+        let thickness = array
+            .data
+            .chunks_exact(face_len)
+            .map(|face| {
+                let border = match &border_color {
+                    Some(color) => &color[..],
+                    None => &face[..pixel_size],
+                };
+                conversion::border_thickness(
+                    face,
+                    size,
+                    pixel_size,
+                    border,
+                    tolerance,
+                    self.0.max_thickness as usize,
+                )
+            })
+            .min()
+            .unwrap_or(0);
+        if thickness == 0 {
+            return Ok(array);
+        }
+
+        let trimmed_size = size - 2 * thickness;
+        array.data = array
+            .data
+            .chunks_exact(face_len)
+            .flat_map(|face| {
+                conversion::copy_region(
+                    face,
+                    size,
+                    pixel_size,
+                    thickness,
+                    thickness,
+                    trimmed_size,
+                    trimmed_size,
+                )
+            })
+            .collect();
+        array.texture_descriptor.size.width = trimmed_size as u32;
+        array.texture_descriptor.size.height = trimmed_size as u32;
+        Ok(array)
+    }
+}
+
+/// Layout of 6 faces packed into one image at arbitrary pixel rectangles, as produced by texture
+/// packers. The rectangles are given in the layer order described in the [crate] docs (+X, -X, +Y,
+/// -Y, +Z, -Z), with `left` and `top` inclusive and `right` and `bottom` exclusive. See
+/// [`SkyboxTextureConversion::make_array_from_atlas`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtlasLayout(pub [Rect<u32>; 6]);
+
+impl SkyboxLayout for AtlasLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        let size = image.texture_descriptor.size;
+        let face_size = |rect: &Rect<u32>| {
+            (
+                rect.right.saturating_sub(rect.left),
+                rect.bottom.saturating_sub(rect.top),
+            )
+        };
+        let expected = face_size(&self.0[0]);
+        for (face, rect) in self.0.iter().enumerate() {
+            let actual = face_size(rect);
+            if actual.0 == 0
+                || actual.1 == 0
+                || rect.right > size.width
+                || rect.bottom > size.height
+            {
+                return Err(SkyboxError::AtlasFaceOutOfBounds {
+                    face,
+                    image_size: (size.width, size.height),
+                });
+            }
+            if actual != expected {
+                return Err(SkyboxError::AtlasFaceSizeMismatch {
+                    face,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        if expected.0 != expected.1 {
+            return Err(SkyboxError::FaceNotSquare {
+                width: expected.0,
+                height: expected.1,
+            });
+        }
+        let pixel_size = uncompressed_pixel_size(image.texture_descriptor.format)?;
+        let mut data = Vec::with_capacity(image.data.len());
+        for rect in &self.0 {
+            data.extend(conversion::copy_region(
+                &image.data,
+                size.width as usize,
+                pixel_size,
+                rect.left as usize,
+                rect.top as usize,
+                expected.0 as usize,
+                expected.1 as usize,
+            ));
+        }
+        let mut array = Image::new(
+            Extent3d {
+                width: expected.0,
+                height: expected.1,
+                depth_or_array_layers: 6,
+            },
+            TextureDimension::D2,
+            data,
+            image.texture_descriptor.format,
+        );
+        array.sampler_descriptor = image.sampler_descriptor.clone();
+        Ok(array)
+    }
+}
+
+/// Layout of faces in a regular grid of `cols` by `rows` equally sized cells, such as the common
+/// 3x2 and 2x3 sprite sheets. `face_indices` gives the cell of each face in the layer order
+/// described in the [crate] docs (+X, -X, +Y, -Y, +Z, -Z), counting cells left to right, then top
+/// to bottom. See [`SkyboxTextureConversion::make_array_from_grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GridLayout {
+    /// Number of columns in the grid.
+    pub cols: u32,
+    /// Number of rows in the grid.
+    pub rows: u32,
+    /// Index of the cell holding each face.
+    pub face_indices: [u32; 6],
+}
+
+impl SkyboxLayout for GridLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        let (cols, rows) = (self.cols, self.rows);
+        let cells = cols.saturating_mul(rows);
+        if cells < 6 {
+            return Err(SkyboxError::InvalidGrid {
+                cols,
+                rows,
+                index: None,
+            });
+        }
+        if let Some(&index) = self.face_indices.iter().find(|&&index| index >= cells) {
+            return Err(SkyboxError::InvalidGrid {
+                cols,
+                rows,
+                index: Some(index),
+            });
+        }
+        let size = image.texture_descriptor.size;
+        if size.width % cols != 0 || size.height % rows != 0 {
+            return Err(SkyboxError::NotDivisibleIntoGrid {
+                image_size: (size.width, size.height),
+                cols,
+                rows,
+            });
+        }
+        let (cell_width, cell_height) = (size.width / cols, size.height / rows);
+        let faces = self.face_indices.map(|index| {
+            let (col, row) = (index % cols, index / cols);
+            Rect {
+                left: col * cell_width,
+                right: (col + 1) * cell_width,
+                top: row * cell_height,
+                bottom: (row + 1) * cell_height,
+            }
+        });
+        AtlasLayout(faces).to_array(image)
+    }
+}
+
+/// Layout of `6N x N` images with the 6 square faces side by side in a single row. `columns` gives
+/// the column of each face in the layer order described in the [crate] docs (+X, -X, +Y, -Y, +Z,
+/// -Z), counting from the left. The default is the faces in layer order from left to right. See
+/// [`SkyboxTextureConversion::make_array_from_horizontal_strip`].
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+/// # use bevy_skybox_cubemap::{HorizontalStripLayout, SkyboxLayout};
+/// // A 6x1 strip where each face is a single pixel whose red channel is its column.
+/// let data = (0..6).flat_map(|column| [column, 0, 0, 255]).collect();
+/// let size = Extent3d { width: 6, height: 1, depth_or_array_layers: 1 };
+/// let strip = Image::new(size, TextureDimension::D2, data, TextureFormat::Rgba8UnormSrgb);
+///
+/// let layout = HorizontalStripLayout { columns: [1, 0, 2, 3, 5, 4] };
+/// let array = layout.to_array(&strip).unwrap();
+/// assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 6);
+/// let faces: Vec<u8> = array.data.chunks(4).map(|pixel| pixel[0]).collect();
+/// assert_eq!(faces, [1, 0, 2, 3, 5, 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HorizontalStripLayout {
+    /// Column of each face, from 0 to 5.
+    pub columns: [u32; 6],
+}
+
+impl Default for HorizontalStripLayout {
+    fn default() -> Self {
+        Self {
+            columns: [0, 1, 2, 3, 4, 5],
+        }
+    }
+}
+
+impl SkyboxLayout for HorizontalStripLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        if let Some(&index) = self.columns.iter().find(|&&index| index >= 6) {
+            return Err(SkyboxError::InvalidGrid {
+                cols: 6,
+                rows: 1,
+                index: Some(index),
+            });
+        }
+        let mut array = image.clone();
+        reinterpret_skybox_strip(&mut array, SplitAxis::Horizontal)?;
+        if self.columns != Self::default().columns {
+            // The strip is now in column order, so each layer only needs moving into place.
+            let layer_len = array.data.len() / 6;
+            array.data = self
+                .columns
+                .iter()
+                .flat_map(|&column| {
+                    let start = column as usize * layer_len;
+                    array.data[start..start + layer_len].iter().copied()
+                })
+                .collect();
+        }
+        Ok(array)
+    }
+}
+
+/// Layout of a cube net in the cross arrangement shown in the [crate] docs: a grid of 4 by 3
+/// equally sized cells, with 4 faces side by side in the middle row, and the top and bottom faces
+/// above and below one of them. The other 6 cells are blank. See [`net_to_array`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NetLayout {
+    /// Column, from 0 to 3, of the middle row face which the top and bottom faces are attached to.
+    /// That face is used as the front (-Z), with the faces to its right being the right (+X), back
+    /// (+Z) and left (-X), wrapping around. Defaults to `1`, which is the canonical net; a net with
+    /// the top and bottom branching off of the third face, like the "shifted net" in the [crate]
+    /// docs, uses `2`.
+    pub top_bottom_column: u32,
+}
+
+impl Default for NetLayout {
+    fn default() -> Self {
+        Self {
+            top_bottom_column: 1,
+        }
+    }
+}
+
+impl SkyboxLayout for NetLayout {
+    fn to_array(&self, image: &Image) -> Result<Image, SkyboxError> {
+        let column = self.top_bottom_column;
+        if column > 3 {
+            // The bottom face's cell is the first one to fall outside of the grid.
+            return Err(SkyboxError::InvalidGrid {
+                cols: 4,
+                rows: 3,
+                index: Some(column.saturating_add(8)),
+            });
+        }
+        // The middle row starts at cell 4, going around the cube from the front to the right.
+        let middle = |offset: u32| 4 + (column + offset) % 4;
+        GridLayout {
+            cols: 4,
+            rows: 3,
+            face_indices: [
+                middle(1),
+                middle(3),
+                column,
+                8 + column,
+                middle(2),
+                middle(0),
+            ],
+        }
+        .to_array(image)
+    }
+}
+
+/// Converts an image of a cube net, with the faces arranged as described by `net_layout`, into a 6
+/// layer array texture which can be used as a skybox texture right away. The faces are copied out
+/// of the net and put in the layer order described in the [crate] docs.
+///
+/// This runs immediately, so it's useful for images which are already in memory and in offline
+/// tools. For textures loaded through the asset server, queue them with
+/// [`SkyboxTextureConversion::make_array_with_layout`] and a [`NetLayout`] instead. Returns an
+/// error if the image can't be split into a 4 by 3 grid or is block-compressed.
+///
+/// ```
+/// # use bevy::{prelude::*, render::render_resource::{Extent3d, TextureDimension, TextureFormat}};
+/// use bevy_skybox_cubemap::{net_to_array, NetLayout};
+/// // A net with 1 pixel cells, where the red channel of each cell is its index.
+/// let data = (0..12).flat_map(|cell| [cell, 0, 0, 255]).collect();
+/// let size = Extent3d { width: 4, height: 3, depth_or_array_layers: 1 };
+/// let net = Image::new(size, TextureDimension::D2, data, TextureFormat::Rgba8UnormSrgb);
+///
+/// let array = net_to_array(&net, NetLayout::default()).unwrap();
+/// assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 6);
+/// let cells: Vec<u8> = array.data.chunks(4).map(|pixel| pixel[0]).collect();
+/// assert_eq!(cells, [6, 4, 1, 9, 7, 5]);
+/// ```
+pub fn net_to_array(net: &Image, net_layout: NetLayout) -> Result<Image, SkyboxError> {
+    net_layout.to_array(net)
+}
+
+/// Like [`reinterpret_stacked_skybox`], but first rearranges the faces of the image according to
+/// `options`.
+pub fn reinterpret_stacked_skybox_with_options(
+    image: &mut Image,
+    options: StripOptions,
+) -> Result<(), SkyboxError> {
+    rearrange_stacked_faces(image, 1, options)?;
+    reinterpret_stacked_skybox(image)
+}
+
+/// Reorders and flips the faces of a stacked image with `frames` sets of 6 faces according to
+/// `options`. The order of the faces is reversed within each frame, so frames stay in order. Images
+/// which already have multiple array layers are left unchanged.
+fn rearrange_stacked_faces(
+    image: &mut Image,
+    frames: u32,
+    options: StripOptions,
+) -> Result<(), SkyboxError> {
+    let size = image.texture_descriptor.size;
+    if size.depth_or_array_layers > 1 || options == StripOptions::default() {
+        return Ok(());
+    }
+    let layers = frames.max(1) * 6;
+    if size.height % layers != 0 {
+        return Err(SkyboxError::NotDivisibleIntoLayers {
+            height: size.height,
+            layers,
+        });
+    }
+    // Check this before touching the data, so the image is left as it was.
+    if size.width != size.height / layers {
+        return Err(SkyboxError::WrongAspectRatio {
+            image_size: (size.width, size.height),
+            expected: (1, layers),
+        });
+    }
+    let pixel_size = uncompressed_pixel_size(image.texture_descriptor.format)?;
+    image.data = conversion::rearrange_faces(
+        &image.data,
+        size.width as usize * pixel_size,
+        (size.height / layers) as usize,
+        options,
+    );
+    Ok(())
+}
+
+/// One of the six faces of a skybox. Each face is named for the world axis direction it covers,
+/// before any rotation from the skybox's transform is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkyboxFace {
+    /// Right face.
+    PosX,
+    /// Left face.
+    NegX,
+    /// Top face.
+    PosY,
+    /// Bottom face.
+    NegY,
+    /// Back face.
+    PosZ,
+    /// Front face.
+    NegZ,
+}
+
+/// The faces of a skybox in the order they are stored as layers of a skybox texture. This is the
+/// order described in the [crate] docs.
+pub const SKYBOX_FACE_ORDER: [SkyboxFace; 6] = [
+    SkyboxFace::PosX,
+    SkyboxFace::NegX,
+    SkyboxFace::PosY,
+    SkyboxFace::NegY,
+    SkyboxFace::PosZ,
+    SkyboxFace::NegZ,
+];
+
+impl SkyboxFace {
+    /// Gets the index of the array texture layer which holds this face.
+    pub const fn layer_index(self) -> usize {
+        match self {
+            SkyboxFace::PosX => 0,
+            SkyboxFace::NegX => 1,
+            SkyboxFace::PosY => 2,
+            SkyboxFace::NegY => 3,
+            SkyboxFace::PosZ => 4,
+            SkyboxFace::NegZ => 5,
+        }
+    }
+
+    /// Gets the face stored in the given array texture layer, if the index is in range.
+    pub fn from_layer_index(index: usize) -> Option<Self> {
+        SKYBOX_FACE_ORDER.get(index).copied()
+    }
+
+    /// Gets the unit vector pointing from the center of the skybox toward the center of this face.
+    pub fn direction(self) -> Vec3 {
+        match self {
+            SkyboxFace::PosX => Vec3::X,
+            SkyboxFace::NegX => -Vec3::X,
+            SkyboxFace::PosY => Vec3::Y,
+            SkyboxFace::NegY => -Vec3::Y,
+            SkyboxFace::PosZ => Vec3::Z,
+            SkyboxFace::NegZ => -Vec3::Z,
+        }
+    }
+}
+
+/// Gets the direction from the center of the skybox toward the point at `uv` on `face`. This is the
+/// inverse of [`skybox_face_for_direction`]; the direction isn't normalized.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{skybox_direction_for_face, skybox_face_for_direction, SkyboxFace};
+/// let direction = skybox_direction_for_face(SkyboxFace::NegX, Vec2::new(0.25, 0.75));
+/// let (face, uv) = skybox_face_for_direction(direction);
+/// assert_eq!(face, SkyboxFace::NegX);
+/// assert!((uv - Vec2::new(0.25, 0.75)).length() < 1e-6);
+/// ```
+pub fn skybox_direction_for_face(face: SkyboxFace, uv: Vec2) -> Vec3 {
+    // Undo the projection in `skybox_face_for_direction` for a point on the face of a cube with
+    // faces at +/-1.
+    let centered = (uv - Vec2::splat(0.5)) * 2.0;
+    match face {
+        SkyboxFace::PosX => Vec3::new(1.0, -centered.y, centered.x),
+        SkyboxFace::NegX => Vec3::new(-1.0, -centered.y, -centered.x),
+        SkyboxFace::PosY => Vec3::new(centered.x, 1.0, -centered.y),
+        SkyboxFace::NegY => Vec3::new(centered.x, -1.0, centered.y),
+        SkyboxFace::PosZ => Vec3::new(-centered.x, -centered.y, 1.0),
+        SkyboxFace::NegZ => Vec3::new(centered.x, -centered.y, -1.0),
+    }
+}
+
+/// Reads the color of a skybox texture in the given direction on the CPU, as linear RGBA. The
+/// direction is in the skybox's own space and doesn't need to be normalized. Returns `None` if the
+/// image isn't a skybox array texture with square faces in a format supported for reading colors
+/// (8 bit RGBA or BGRA, or `Rgba32Float`), or for the zero vector. Only the first frame of
+/// animated skyboxes is used.
+///
+/// This picks the nearest texel, using the same face and UVs as the shader (see
+/// [`skybox_face_for_direction`]), without any filtering or blending across seams. It's meant for
+/// occasional queries, such as picking a fog color toward the camera's view or checking that a
+/// texture is laid out the way you expect. For example, every axis of
+/// [`SkyboxMaterial::debug_texture`] shows its own face:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{sample_skybox, SkyboxFace, SkyboxMaterial, SKYBOX_FACE_ORDER};
+/// let mut images = Assets::<Image>::default();
+/// let handle = SkyboxMaterial::debug_texture(&mut images);
+/// let image = images.get(handle).unwrap();
+/// for face in SKYBOX_FACE_ORDER {
+///     // The debug texture colors each face by its axis: brighter for the positive direction.
+///     let color = sample_skybox(image, face.direction()).unwrap();
+///     let axis = face.layer_index() / 2;
+///     let expected = if face.layer_index() % 2 == 0 { 1.0 } else { 0.35 };
+///     assert!((color[axis] - expected).abs() < 0.01, "{:?}: {:?}", face, color);
+///     for other in (0..3).filter(|&other| other != axis) {
+///         assert_eq!(color[other], 0.0, "{:?}: {:?}", face, color);
+///     }
+/// }
+/// // The white marker in the top left corner of the +X face is toward -Z and +Y.
+/// let corner = sample_skybox(image, Vec3::new(1.0, 0.9, -0.9)).unwrap();
+/// assert!((corner - Vec4::ONE).abs().max_element() < 0.01);
+/// ```
+pub fn sample_skybox(image: &Image, direction: Vec3) -> Option<Vec4> {
+    let size = image.texture_descriptor.size;
+    if size.depth_or_array_layers < 6 || size.width != size.height || size.width == 0 {
+        return None;
+    }
+    if direction == Vec3::ZERO || !direction.is_finite() {
+        return None;
+    }
+    let format = image.texture_descriptor.format;
+    let pixel_size = uncompressed_pixel_size(format).ok()?;
+    let face_size = size.width as usize;
+    let (face, uv) = skybox_face_for_direction(direction);
+    let texel = |coordinate: f32| ((coordinate * face_size as f32) as usize).min(face_size - 1);
+    // Each layer is stored with all of its mip levels before the next layer, like in
+    // `SkyboxMaterial::face_pixels`.
+    let mip_level_count = image.texture_descriptor.mip_level_count;
+    let layer_start = face.layer_index() * layer_byte_len(size.width, mip_level_count, format);
+    let offset = layer_start + (texel(uv.y) * face_size + texel(uv.x)) * pixel_size;
+    let pixel = image.data.get(offset..offset + pixel_size)?;
+    linear_texel(format, pixel)
+}
+
+/// Computes 9 spherical harmonics coefficients (bands 0 to 2) approximating the diffuse lighting
+/// of a skybox texture, for cheap image based lighting in your own shaders. Returns `None` if the
+/// image isn't a skybox array texture with square faces in a format supported for reading colors
+/// (8 bit RGBA or BGRA, or `Rgba32Float`). Only the first frame of animated skyboxes is used.
+///
+/// The coefficients are in linear RGB and are scaled so that [`evaluate_irradiance_sh`] gives the
+/// light a white, perfectly diffuse surface facing the given normal reflects; multiply it by the
+/// surface's color. They are in the skybox's own space, before any rotation is applied. The
+/// coefficients are ordered as `Y(0,0)`, `Y(1,-1)`, `Y(1,0)`, `Y(1,1)`, `Y(2,-2)`, `Y(2,-1)`,
+/// `Y(2,0)`, `Y(2,1)`, `Y(2,2)` in the usual real basis.
+///
+/// This visits every texel of the texture, so for large textures run it once, e.g. on the
+/// [`AsyncComputeTaskPool`]:
+///
+/// ```no_run
+/// # use bevy::{prelude::*, tasks::{AsyncComputeTaskPool, Task}};
+/// # use bevy_skybox_cubemap::skybox_irradiance_sh;
+/// fn start_baking(texture: &Image, task_pool: &AsyncComputeTaskPool) -> Task<Option<[Vec3; 9]>> {
+///     let texture = texture.clone();
+///     task_pool.spawn(async move { skybox_irradiance_sh(&texture) })
+/// }
+/// ```
+pub fn skybox_irradiance_sh(image: &Image) -> Option<[Vec3; 9]> {
+    let size = image.texture_descriptor.size;
+    if size.depth_or_array_layers < 6 || size.width != size.height || size.width == 0 {
+        return None;
+    }
+    let texels = linear_texels(image)?;
+    let face_size = size.width as usize;
+    // Convolving with the clamped cosine lobe scales each band by these factors, divided by pi to
+    // turn irradiance into the light reflected by a white diffuse surface.
+    let band_scale = [1.0, 2.0 / 3.0, 1.0 / 4.0];
+    let mut coefficients = [Vec3::ZERO; 9];
+    for (layer, face) in SKYBOX_FACE_ORDER.iter().enumerate() {
+        let face_texels =
+            &texels[layer * face_size * face_size..(layer + 1) * face_size * face_size];
+        for (index, texel) in face_texels.iter().enumerate() {
+            let uv = Vec2::new(
+                ((index % face_size) as f32 + 0.5) / face_size as f32,
+                ((index / face_size) as f32 + 0.5) / face_size as f32,
+            );
+            let direction = skybox_direction_for_face(*face, uv);
+            // Solid angle covered by the texel: its area on the cube face, which spans 2 units,
+            // projected onto the unit sphere.
+            let length = direction.length();
+            let solid_angle = (2.0 / face_size as f32).powi(2) / (length * length * length);
+            let basis = sh9_basis(direction / length);
+            for (coefficient, basis) in coefficients.iter_mut().zip(basis) {
+                *coefficient += texel.truncate() * basis * solid_angle;
+            }
+        }
+    }
+    for (index, coefficient) in coefficients.iter_mut().enumerate() {
+        // Coefficient 0 is band 0, 1 to 3 are band 1, and 4 to 8 are band 2.
+        let band = match index {
+            0 => 0,
+            1..=3 => 1,
+            _ => 2,
+        };
+        *coefficient *= band_scale[band];
+    }
+    Some(coefficients)
+}
+
+/// Evaluates spherical harmonics coefficients from [`skybox_irradiance_sh`] for a surface normal,
+/// giving the linear RGB light the skybox reflects off a white, perfectly diffuse surface facing
+/// that way.
+pub fn evaluate_irradiance_sh(coefficients: &[Vec3; 9], normal: Vec3) -> Vec3 {
+    coefficients
+        .iter()
+        .zip(sh9_basis(normal.normalize_or_zero()))
+        .map(|(coefficient, basis)| *coefficient * basis)
+        .sum()
+}
+
+/// Evaluates the first 9 real spherical harmonics basis functions for a unit direction.
+fn sh9_basis(direction: Vec3) -> [f32; 9] {
+    let Vec3 { x, y, z } = direction;
+    [
+        0.282_095,
+        0.488_603 * y,
+        0.488_603 * z,
+        0.488_603 * x,
+        1.092_548 * x * y,
+        1.092_548 * y * z,
+        0.315_392 * (3.0 * z * z - 1.0),
+        1.092_548 * x * z,
+        0.546_274 * (x * x - y * y),
+    ]
+}
+
+/// Finds which face of the skybox a direction points at, and the UV coordinates within that face's
+/// layer of the texture. The direction is in the skybox's own space, i.e. before the rotation of
+/// the skybox's transform or [`SkyboxMaterial::rotation`] is applied, and doesn't need to be
+/// normalized. UVs range from 0 to 1, with the origin at the top left of the face's layer.
+///
+/// This is the same math the skybox shader uses to pick the texel to draw, so it can be used to line
+/// up gameplay logic with features of the sky texture. Returns NaN UVs for the zero vector.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{skybox_face_for_direction, SkyboxFace};
+/// let (face, uv) = skybox_face_for_direction(Vec3::new(0.0, 1.0, 0.0));
+/// assert_eq!(face, SkyboxFace::PosY);
+/// assert_eq!(uv, Vec2::new(0.5, 0.5));
+/// ```
+pub fn skybox_face_for_direction(direction: Vec3) -> (SkyboxFace, Vec2) {
+    // GLSL's sign returns 0 for 0, unlike f32::signum.
+    fn sign(value: f32) -> f32 {
+        if value > 0.0 {
+            1.0
+        } else if value < 0.0 {
+            -1.0
+        } else {
+            0.0
+        }
+    }
+
+    // Keep in sync with sampleCubeHacky in skybox.frag.
+    let abs = direction.abs();
+    let (face, max_adjust, uv) = if abs.z >= abs.x && abs.z >= abs.y {
+        let face = if direction.z < 0.0 {
+            SkyboxFace::NegZ
+        } else {
+            SkyboxFace::PosZ
+        };
+        let uv = Vec2::new(direction.x * -sign(direction.z), -direction.y);
+        (face, 0.5 / abs.z, uv)
+    } else if abs.y >= abs.x {
+        let face = if direction.y < 0.0 {
+            SkyboxFace::NegY
+        } else {
+            SkyboxFace::PosY
+        };
+        let uv = Vec2::new(direction.x * sign(direction.y), -direction.z);
+        (face, 0.5 / direction.y, uv)
+    } else {
+        let face = if direction.x < 0.0 {
+            SkyboxFace::NegX
+        } else {
+            SkyboxFace::PosX
+        };
+        let uv = Vec2::new(direction.z, direction.y * -sign(direction.x));
+        (face, 0.5 / direction.x, uv)
+    };
+    (face, uv * max_adjust + Vec2::splat(0.5))
+}
+
+/// Converts a vertically stacked skybox image into an array texture like
+/// [`reinterpret_stacked_skybox`], rotating each face by the corresponding entry of `rotations`.
+/// This is the conversion used by [`SkyboxTextureConversion::make_array_with_orientations`].
+pub fn rotate_stacked_skybox_faces(
+    mut image: Image,
+    rotations: [FaceRotation; 6],
+) -> Result<Image, SkyboxError> {
+    reinterpret_stacked_skybox(&mut image)?;
+    let size = image.texture_descriptor.size;
+    if size.width != size.height {
+        return Err(SkyboxError::FaceNotSquare {
+            width: size.width,
+            height: size.height,
+        });
+    }
+    let pixel_size = uncompressed_pixel_size(image.texture_descriptor.format)?;
+    conversion::rotate_faces(&mut image.data, size.width as usize, pixel_size, rotations);
+    Ok(image)
+}
+
+/// Converts an image with fewer than 4 channels, or with integer channels, to
+/// [`TextureFormat::Rgba8UnormSrgb`]. Returns `true` if the image was converted.
+///
+/// The skybox shader samples its texture as filterable floats with 4 channels, which images loaded
+/// from grayscale or 16 bit files don't provide: Bevy loads them as `R8Unorm`, `Rg8Unorm`, or one
+/// of the `Uint` formats. Single channel images are treated as grayscale and two channel images as
+/// grayscale with alpha; missing alpha is filled in as opaque. 16 bit channels are reduced to 8
+/// bits. Images in other formats are left unchanged.
+pub fn normalize_skybox_format(image: &mut Image) -> bool {
+    use TextureFormat::*;
+    let format = image.texture_descriptor.format;
+    // Number of channels and bytes per channel of the formats which need converting.
+    let (channels, channel_size) = match format {
+        R8Unorm => (1, 1),
+        Rg8Unorm => (2, 1),
+        R16Uint => (1, 2),
+        Rg16Uint => (2, 2),
+        Rgba16Uint => (4, 2),
+        _ => return false,
+    };
+    // Bevy stores 16 bit channels in native byte order, which is what the conversion expects.
+    image.data = conversion::expand_to_rgba8(&image.data, channels, channel_size);
+    image.texture_descriptor.format = Rgba8UnormSrgb;
+    true
+}
+
+/// Changes an image to `format`, recreating its texture descriptor. Used for formats declared with
+/// [`SkyboxTextureConversion::expect_format`].
+///
+/// Switching between the linear and sRGB variants of a format only changes the descriptor, so the
+/// stored values are reinterpreted in the other color space rather than converted, which is what
+/// fixes an image decoded with the wrong color space. Images in the 8 bit RGBA and BGRA formats
+/// can also be changed to each other, swapping their red and blue channels. Other changes return
+/// [`SkyboxError::FormatNotConvertible`] and leave the image unchanged.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+/// # use bevy_skybox_cubemap::convert_skybox_format;
+/// let mut image = Image::new(
+///     Extent3d { width: 1, height: 1, depth_or_array_layers: 1 },
+///     TextureDimension::D2,
+///     vec![10, 20, 30, 255],
+///     TextureFormat::Rgba8UnormSrgb,
+/// );
+/// convert_skybox_format(&mut image, TextureFormat::Rgba8Unorm).unwrap();
+/// assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+/// assert_eq!(image.data, [10, 20, 30, 255]);
+///
+/// convert_skybox_format(&mut image, TextureFormat::Bgra8UnormSrgb).unwrap();
+/// assert_eq!(image.data, [30, 20, 10, 255]);
+///
+/// assert!(convert_skybox_format(&mut image, TextureFormat::Rgba16Float).is_err());
+/// ```
+pub fn convert_skybox_format(image: &mut Image, format: TextureFormat) -> Result<(), SkyboxError> {
+    use TextureFormat::*;
+    let from = image.texture_descriptor.format;
+    if from == format || color_space_variant(from, format.describe().srgb) == Some(format) {
+        image.texture_descriptor.format = format;
+        return Ok(());
+    }
+    match (from, format) {
+        (
+            Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb,
+            Rgba8Unorm | Rgba8UnormSrgb | Bgra8Unorm | Bgra8UnormSrgb,
+        ) => {
+            // Only a change between RGBA and BGRA gets here, as the rest are handled above.
+            for pixel in image.data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            image.texture_descriptor.format = format;
+            Ok(())
+        }
+        _ => Err(SkyboxError::FormatNotConvertible { from, to: format }),
+    }
+}
+
+/// Gets the size in bytes of a single pixel, or an error if the format is block-compressed and
+/// therefore doesn't have individually addressable pixels.
+fn uncompressed_pixel_size(format: TextureFormat) -> Result<usize, SkyboxError> {
+    let info = format.describe();
+    if info.block_dimensions != (1, 1) {
+        return Err(SkyboxError::UnsupportedFormat(format));
+    }
+    Ok(info.block_size as usize)
+}
+
+/// System to handle reinterpreting an Nx6N vertical texture stack as an array of textures suitable
+/// for a skybox, and to run and collect async conversions.
+fn convert_skyboxes(
+    mut conversions: ResMut<SkyboxTextureConversion>,
+    mut textures: ResMut<Assets<Image>>,
+    mut events: EventReader<AssetEvent<Image>>,
+    task_pool: Res<AsyncComputeTaskPool>,
+    asset_server: Res<AssetServer>,
+    render_device: Option<Res<RenderDevice>>,
+    mut conversion_events: EventWriter<SkyboxConversionEvent>,
+) {
+    let conversions = &mut *conversions;
+    let mut sent = Vec::new();
+    // Errors are logged as well as sent, so that they aren't missed when nothing reads the events.
+    let fail = |sent: &mut Vec<_>, handle: &Handle<Image>, error: SkyboxError| {
+        error!("Failed to convert Skybox Texture {:?}: {}", handle, error);
+        sent.push(SkyboxConversionEvent::Failed {
+            handle: handle.clone_weak(),
+            error,
+        });
+    };
+    // Array textures with more layers than the device supports can't be created, so catch them
+    // here with a clear error rather than letting creating the texture fail.
+    let max_layers = render_device.map(|device| device.limits().max_texture_array_layers);
+    let check_layers = |layers: u32| match max_layers {
+        Some(max) if layers > max => Err(SkyboxError::TooManyLayers { layers, max }),
+        _ => Ok(()),
+    };
+    let formats = conversions.formats.clone();
+    let apply_format = |handle: &Handle<Image>, image: &mut Image| match formats.get(&handle.id) {
+        Some(format) => convert_skybox_format(image, *format),
+        None => Ok(()),
+    };
+
+    // Re-queue converted textures which have been reloaded. Converting a texture also produces a
+    // modified event, so only textures which are back to being a single layer need converting.
+    for event in events.iter() {
+        match event {
+            AssetEvent::Modified { handle } => {
+                let (method, priority) = match conversions.converted.get(&handle.id) {
+                    Some(converted) => converted.clone(),
+                    None => continue,
+                };
+                let reloaded = textures.get(handle).map_or(false, |texture| {
+                    texture.texture_descriptor.size.depth_or_array_layers == 1
+                });
+                if reloaded && !conversions.is_queued(handle) {
+                    info!("Skybox Texture {:?} was reloaded, converting again", handle);
+                    conversions.converted.remove(&handle.id);
+                    conversions.queue(PendingConversion {
+                        handle: handle.clone_weak(),
+                        method,
+                        priority,
+                    });
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                // Textures taken out for asynchronous conversions are still converted.
+                if !conversions.taken.remove(&handle.id) {
+                    conversions.converted.remove(&handle.id);
+                }
+            }
+            _ => {}
+        }
+    }
+    sent.extend(
+        conversions
+            .newly_queued
+            .drain(..)
+            .map(|handle| SkyboxConversionEvent::Queued { handle }),
+    );
+
+    let mut index = 0;
+    let mut converted = 0;
+    while let Some(pending) = conversions.pending.get(index) {
+        if conversions
+            .max_conversions_per_frame
+            .map_or(false, |max| converted >= max)
+        {
+            break;
+        }
+        // Assembled textures wait for all of their faces instead of the placeholder texture.
+        if let ConversionMethod::Assemble {
+            faces,
+            regions,
+            rotations,
+        } = &pending.method
+        {
+            let (faces, regions, rotations) = (faces.clone(), *regions, *rotations);
+            if let Some(failed) = faces
+                .iter()
+                .find(|face| asset_server.get_load_state(*face) == LoadState::Failed)
+            {
+                let pending = conversions.pending.remove(index);
+                let err = SkyboxError::TextureLoadFailed {
+                    path: asset_server
+                        .get_handle_path(failed)
+                        .map(|path| path.path().display().to_string()),
+                };
+                fail(&mut sent, &pending.handle, err);
+                continue;
+            }
+            if !faces.iter().all(|face| textures.contains(face)) {
+                index += 1;
+                continue;
+            }
+            let pending = conversions.pending.remove(index);
+            converted += 1;
+            let images = [0, 1, 2, 3, 4, 5].map(|layer| textures.get(&faces[layer]).unwrap());
+            sent.push(SkyboxConversionEvent::Loaded {
+                handle: pending.handle.clone_weak(),
+                bytes: images.iter().map(|image| image.data.len()).sum(),
+            });
+            let result = regions
+                .iter()
+                .zip(images)
+                .enumerate()
+                .map(|(face, (region, image))| {
+                    region
+                        .map(|rect| crop_skybox_face(image, rect, face))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .and_then(|cropped| {
+                    let images = [0, 1, 2, 3, 4, 5]
+                        .map(|layer| cropped[layer].as_ref().unwrap_or(images[layer]));
+                    assemble_skybox(images, rotations)
+                })
+                .and_then(|mut image| {
+                    check_layers(image.texture_descriptor.size.depth_or_array_layers)?;
+                    apply_format(&pending.handle, &mut image)?;
+                    Ok(image)
+                });
+            match result {
+                Ok(image) => {
+                    sent.push(SkyboxConversionEvent::Converted {
+                        handle: pending.handle.clone_weak(),
+                        bytes: image.data.len(),
+                    });
+                    if let Some(texture) = textures.get_mut(&pending.handle) {
+                        info!("Assembled Skybox Texture {:?}", pending.handle);
+                        *texture = image;
+                    }
+                    // Only weak handles to the faces are kept, so they can be unloaded now that
+                    // they've been copied. The placeholder isn't loaded from a file, so it's never
+                    // reloaded and assembled again.
+                    conversions.converted.insert(
+                        pending.handle.id,
+                        (
+                            ConversionMethod::Assemble {
+                                faces: faces.map(|face| face.clone_weak()),
+                                regions,
+                                rotations,
+                            },
+                            pending.priority,
+                        ),
+                    );
+                }
+                Err(err) => fail(&mut sent, &pending.handle, err),
+            }
+            continue;
+        }
+
+        // Check each texture in the pending queue to see if it is loaded yet.
+        let (pending, texture) = match textures.get_mut(&pending.handle) {
+            // If it's loaded, take it out of the queue.
+            Some(texture) => (conversions.pending.remove(index), texture),
+            // Textures which failed to load never will, so stop waiting for them.
+            None if asset_server.get_load_state(&pending.handle) == LoadState::Failed => {
+                let pending = conversions.pending.remove(index);
+                let err = SkyboxError::TextureLoadFailed {
+                    path: asset_server
+                        .get_handle_path(&pending.handle)
+                        .map(|path| path.path().display().to_string()),
+                };
+                fail(&mut sent, &pending.handle, err);
+                continue;
+            }
+            None => {
+                index += 1;
+                continue;
+            }
+        };
+        let handle = pending.handle;
+        converted += 1;
+        sent.push(SkyboxConversionEvent::Loaded {
+            handle: handle.clone_weak(),
+            bytes: texture.data.len(),
+        });
+
+        let format = texture.texture_descriptor.format;
+        // Textures loaded from layered formats (KTX2, DDS, etc.) are already array textures.
+        // Reinterpreting them as stacked 2d images would corrupt them, so leave them as-is.
+        let layers = texture.texture_descriptor.size.depth_or_array_layers;
+        if layers > 1 {
+            // Animated skyboxes have 6 layers per frame.
+            let expected = match &pending.method {
+                ConversionMethod::Stacked { frames, .. } => (*frames).max(1) * 6,
+                _ => 6,
+            };
+            if layers != expected {
+                fail(
+                    &mut sent,
+                    &handle,
+                    SkyboxError::WrongLayerCount { layers, expected },
+                );
+                continue;
+            }
+            info!(
+                "Skybox Texture {:?} already has {} layers, skipping conversion",
+                handle, layers,
+            );
+            if let Err(err) = apply_format(&handle, texture) {
+                fail(&mut sent, &handle, err);
+                continue;
+            }
+            sent.push(SkyboxConversionEvent::Converted {
+                handle: handle.clone_weak(),
+                bytes: texture.data.len(),
+            });
+            continue;
+        }
+
+        if normalize_skybox_format(texture) {
+            info!(
+                "Converted Skybox Texture {:?} from {:?} to {:?}",
+                handle, format, texture.texture_descriptor.format,
+            );
+        }
+
+        let frames = match &pending.method {
+            ConversionMethod::Stacked { frames, options } => {
+                // Catch images which can't possibly be a stack of faces before they cause
+                // confusing failures further down. Other layouts validate their own input.
+                let size = texture.texture_descriptor.size;
+                if size.width == 0 || size.height < 6 || size.height % 6 != 0 {
+                    warn!(
+                        "Skybox Texture {:?} is {}x{}, which can't be split into 6 faces: the \
+                         width must be non-zero and the height a non-zero multiple of 6. Skipping \
+                         conversion.",
+                        handle, size.width, size.height,
+                    );
+                    sent.push(SkyboxConversionEvent::Failed {
+                        handle: handle.clone_weak(),
+                        error: SkyboxError::NotDivisibleBySix {
+                            height: size.height,
+                        },
+                    });
+                    continue;
+                }
+                if let Err(err) = check_layers((*frames).max(1) * 6)
+                    .and_then(|()| rearrange_stacked_faces(texture, *frames, *options))
+                {
+                    fail(&mut sent, &handle, err);
+                    continue;
+                }
+                *frames
+            }
+            ConversionMethod::Assemble { .. } => {
+                unreachable!("assembled textures are handled above")
+            }
+            ConversionMethod::Async(converter) => {
+                info!("Converting Skybox Texture {:?} asynchronously", handle);
+                // Take the image rather than copying it, since it's replaced when the conversion
+                // is done anyway.
+                let source = match textures.remove(&handle) {
+                    Some(source) => source,
+                    None => continue,
+                };
+                conversions.taken.insert(handle.id);
+                let convert = converter.clone();
+                let task = task_pool.spawn(async move { convert(source) });
+                conversions.tasks.push((handle.clone(), task));
+                conversions
+                    .converted
+                    .insert(handle.id, (pending.method, pending.priority));
+                continue;
+            }
+        };
+
+        info!(
+            "Reinterpreting as Skybox Texture {:?}: len: {}",
+            handle,
+            texture.data.len(),
+        );
+        match reinterpret_stacked_skybox_frames(texture, frames)
+            .and_then(|()| apply_format(&handle, texture))
+        {
+            Ok(()) => {
+                sent.push(SkyboxConversionEvent::Converted {
+                    handle: handle.clone_weak(),
+                    bytes: texture.data.len(),
+                });
+                conversions
+                    .converted
+                    .insert(handle.id, (pending.method, pending.priority));
+            }
+            Err(err) => fail(&mut sent, &handle, err),
+        }
+    }
+
+    // Collect any async conversions which have finished.
+    let mut index = 0;
+    while let Some((_, task)) = conversions.tasks.get_mut(index) {
+        let result = match future::block_on(future::poll_once(task)) {
+            Some(result) => result,
+            None => {
+                index += 1;
+                continue;
+            }
+        };
+        let (handle, _) = conversions.tasks.remove(index);
+        let result = result.and_then(|mut image| {
+            let layers = image.texture_descriptor.size.depth_or_array_layers;
+            // A single layer image would look like a reloaded texture and be converted again.
+            if layers <= 1 {
+                return Err(SkyboxError::WrongLayerCount {
+                    layers,
+                    expected: 6,
+                });
+            }
+            check_layers(layers)?;
+            apply_format(&handle, &mut image)?;
+            Ok(image)
+        });
+        match result {
+            Ok(image) => {
+                sent.push(SkyboxConversionEvent::Converted {
+                    handle: handle.clone_weak(),
+                    bytes: image.data.len(),
+                });
+                info!("Finished converting Skybox Texture {:?}", handle);
+                textures.set_untracked(&handle, image);
+            }
+            Err(err) => {
+                conversions.failed.insert(handle.id);
+                conversions.converted.remove(&handle.id);
+                fail(&mut sent, &handle, err);
+            }
+        }
+    }
+    conversion_events.send_batch(sent.into_iter());
+}
+
+/// Event sent as textures queued in [`SkyboxTextureConversion`] make their way through conversion,
+/// e.g. for showing the progress of loading skyboxes on a loading screen. Each queued texture gets
+/// a `Queued` event, then a `Loaded` event once its image has loaded, and finally either a
+/// `Converted` or a `Failed` event. Textures which fail to load skip straight to `Failed`.
+///
+/// Events are only sent while [`SkyboxPlugin::auto_convert`] is enabled. Textures which are
+/// converted again after being reloaded go through the events again, and textures removed with
+/// [`SkyboxTextureConversion::cancel`] get no further events.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::SkyboxConversionEvent;
+/// /// Tracks the fraction of queued skybox textures which are done.
+/// #[derive(Default)]
+/// struct SkyboxProgress {
+///     queued: usize,
+///     done: usize,
+/// }
+///
+/// fn track_progress(
+///     mut events: EventReader<SkyboxConversionEvent>,
+///     mut progress: ResMut<SkyboxProgress>,
+/// ) {
+///     for event in events.iter() {
+///         match event {
+///             SkyboxConversionEvent::Queued { .. } => progress.queued += 1,
+///             SkyboxConversionEvent::Loaded { .. } => {}
+///             SkyboxConversionEvent::Converted { .. } | SkyboxConversionEvent::Failed { .. } => {
+///                 progress.done += 1
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SkyboxConversionEvent {
+    /// The texture was queued for conversion.
+    Queued {
+        /// The queued texture.
+        handle: Handle<Image>,
+    },
+    /// The texture's image has loaded, and its conversion is starting. For a [`SkyboxAssembler`],
+    /// this is once all of the faces have loaded.
+    Loaded {
+        /// The loaded texture.
+        handle: Handle<Image>,
+        /// Size of the loaded image data in bytes, or of all of the faces for a
+        /// [`SkyboxAssembler`].
+        bytes: usize,
+    },
+    /// The texture has been converted and is ready to use in a [`SkyboxMaterial`].
+    Converted {
+        /// The converted texture.
+        handle: Handle<Image>,
+        /// Size of the converted image data in bytes.
+        bytes: usize,
+    },
+    /// The texture failed to load or convert. The error is logged too.
+    Failed {
+        /// The texture which failed.
+        handle: Handle<Image>,
+        /// What went wrong.
+        error: SkyboxError,
+    },
+}
+
+/// Switches skybox textures between the sRGB and linear variants of their format to match
+/// [`SkyboxMaterial::is_srgb`]. The image is only touched if its format actually needs to change, so
+/// this doesn't cause textures to be uploaded again every frame.
+fn apply_skybox_color_spaces(
+    materials: Res<Assets<SkyboxMaterial>>,
+    mut images: ResMut<Assets<Image>>,
+    conversions: Option<Res<SkyboxTextureConversion>>,
+) {
+    for (_, material) in materials.iter() {
+        let handle = match &material.texture {
+            Some(handle) => handle,
+            None => continue,
+        };
+        // Formats declared for the texture take precedence over the material.
+        if conversions.as_ref().map_or(false, |conversions| {
+            conversions.expected_format(handle).is_some()
+        }) {
+            continue;
+        }
+        let format = match images.get(handle).and_then(|image| {
+            color_space_variant(image.texture_descriptor.format, material.is_srgb)
+        }) {
+            Some(format) => format,
+            None => continue,
+        };
+        if let Some(image) = images.get_mut(handle) {
+            debug!(
+                "Switching skybox texture {:?} from {:?} to {:?}",
+                handle, image.texture_descriptor.format, format,
+            );
+            image.texture_descriptor.format = format;
+        }
+    }
+}
+
+/// Returns the sRGB (if `srgb` is set) or linear variant of `format`, or `None` if `format` is
+/// already the requested variant or has no variant with the other color space.
+fn color_space_variant(format: TextureFormat, srgb: bool) -> Option<TextureFormat> {
+    use TextureFormat::*;
+    let (linear, srgb_format) = match format {
+        Rgba8Unorm | Rgba8UnormSrgb => (Rgba8Unorm, Rgba8UnormSrgb),
+        Bgra8Unorm | Bgra8UnormSrgb => (Bgra8Unorm, Bgra8UnormSrgb),
+        Bc1RgbaUnorm | Bc1RgbaUnormSrgb => (Bc1RgbaUnorm, Bc1RgbaUnormSrgb),
+        Bc2RgbaUnorm | Bc2RgbaUnormSrgb => (Bc2RgbaUnorm, Bc2RgbaUnormSrgb),
+        Bc3RgbaUnorm | Bc3RgbaUnormSrgb => (Bc3RgbaUnorm, Bc3RgbaUnormSrgb),
+        Bc7RgbaUnorm | Bc7RgbaUnormSrgb => (Bc7RgbaUnorm, Bc7RgbaUnormSrgb),
+        _ => return None,
+    };
+    let wanted = if srgb { srgb_format } else { linear };
+    if wanted == format {
+        None
+    } else {
+        Some(wanted)
+    }
+}
+
+/// Creates the layout of the skybox material's bind group. `filterable` selects whether the skybox
+/// texture is bound as a filterable texture with a filtering sampler, or a non-filterable texture
+/// with a non-filtering sampler. `dimension` is the view dimension of the skybox texture.
+fn skybox_bind_group_layout(
+    render_device: &RenderDevice,
+    filterable: bool,
+    dimension: TextureViewDimension,
+) -> BindGroupLayout {
+    render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+        entries: &[
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: BufferSize::new(SkyboxUniform::std140_size_static() as u64),
+                },
+                count: None,
+            },
+            // Texture
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable },
+                    view_dimension: dimension,
+                },
+                count: None,
+            },
+            // Texture Sampler
+            BindGroupLayoutEntry {
+                binding: 2,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(if filterable {
+                    SamplerBindingType::Filtering
+                } else {
+                    SamplerBindingType::NonFiltering
+                }),
+                count: None,
+            },
+            // Overlay Texture
+            BindGroupLayoutEntry {
+                binding: 3,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2Array,
+                },
+                count: None,
+            },
+            // Overlay Texture Sampler
+            BindGroupLayoutEntry {
+                binding: 4,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
+            // Color Grading LUT
+            BindGroupLayoutEntry {
+                binding: 5,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D3,
+                },
+                count: None,
+            },
+            // Color Grading LUT Sampler
+            BindGroupLayoutEntry {
+                binding: 6,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
+            // Low Resolution Texture, sampled with the texture's sampler
+            BindGroupLayoutEntry {
+                binding: 7,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable },
+                    view_dimension: TextureViewDimension::D2Array,
+                },
+                count: None,
+            },
+            // Mask Texture
+            BindGroupLayoutEntry {
+                binding: 8,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    multisampled: false,
+                    sample_type: TextureSampleType::Float { filterable: true },
+                    view_dimension: TextureViewDimension::D2,
+                },
+                count: None,
+            },
+            // Mask Texture Sampler
+            BindGroupLayoutEntry {
+                binding: 9,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            },
+        ],
+        label: None,
+    })
+}
+
+/// Render world resource with the material layouts used instead of the default one, created with
+/// the render device like the [`MaterialPipeline`]'s layout.
+struct SkyboxBindGroupLayouts {
+    /// Layout for materials whose texture can't be filtered.
+    unfilterable: BindGroupLayout,
+    /// Layout for materials whose texture is bound as a cube texture.
+    cube: BindGroupLayout,
+}
+
+impl FromWorld for SkyboxBindGroupLayouts {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.get_resource::<RenderDevice>().unwrap();
+        Self {
+            unfilterable: skybox_bind_group_layout(
+                render_device,
+                false,
+                TextureViewDimension::D2Array,
+            ),
+            cube: skybox_bind_group_layout(render_device, true, TextureViewDimension::Cube),
+        }
+    }
+}
+
+/// Resource for converting stacked skybox textures which only exist on the GPU, such as skies
+/// rendered into a texture, into skybox array textures without reading them back to the CPU. The
+/// [`SkyboxPlugin`] adds this resource. Unlike [`SkyboxTextureConversion`], which rearranges the
+/// CPU copy of an image, this copies the rows of the source texture into the layers of a new GPU
+/// texture in the render world.
+///
+/// [`make_array`](Self::make_array) returns a handle to the converted texture to use as a
+/// [`SkyboxMaterial::texture`]. That texture only exists in the render world, so it isn't in
+/// `Assets<Image>` and has no CPU data: CPU-side helpers like [`SkyboxMaterial::face_pixels`] or
+/// [`skybox_irradiance_sh`] don't work with it, and [`SkyboxMaterial::cube_sampling`] isn't
+/// supported.
+///
+/// The copy is recorded by the [`SKYBOX_GPU_CONVERSION_NODE`] render graph node, which runs before
+/// the main pass. Textures rendered into by your own render graph nodes are only finished once
+/// those nodes have run, so add an edge from them to this node; otherwise the sky may be copied
+/// before it has been drawn. The copy is made once, in the first frame both textures are on the
+/// GPU. Call [`refresh`](Self::refresh) to copy the source again, e.g. after rendering a new sky.
+///
+/// The converted texture is freed when its source is removed from `Assets<Image>`, or when it's
+/// passed to [`remove`](Self::remove). The returned handle is weak, so dropping it frees nothing.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{SkyboxGpuConversion, SkyboxMaterial};
+/// fn make_sky(
+///     rendered_sky: Res<RenderedSky>,
+///     conversion: Res<SkyboxGpuConversion>,
+///     images: Res<Assets<Image>>,
+///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+/// ) {
+///     match conversion.make_array(&rendered_sky.0, &images) {
+///         Ok(texture) => {
+///             skyboxes.add(SkyboxMaterial::from_texture(texture));
+///         }
+///         Err(err) => error!("Can't convert the rendered sky: {}", err),
+///     }
+/// }
+/// # struct RenderedSky(Handle<Image>);
+/// ```
+#[derive(Clone, Default)]
+pub struct SkyboxGpuConversion {
+    /// Shared between the main world and the render world.
+    state: Arc<RwLock<GpuConversionState>>,
+}
+
+#[derive(Default)]
+struct GpuConversionState {
+    /// Weak handle to the source of each conversion, by the id of the target handle.
+    sources: HashMap<HandleId, Handle<Image>>,
+    /// Descriptors of the textures each conversion creates, by the id of the target handle.
+    targets: HashMap<HandleId, TextureDescriptor<'static>>,
+    /// Targets waiting for a copy of their source.
+    copies: HashSet<HandleId>,
+    /// Targets which have been copied into at least once.
+    converted: HashSet<HandleId>,
+    /// Removed targets whose textures are still to be freed in the render world.
+    released: Vec<HandleId>,
+}
+
+impl GpuConversionState {
+    /// Forgets about a target, so its texture is freed. Returns whether it was a target.
+    fn release(&mut self, target: HandleId) -> bool {
+        self.sources.remove(&target);
+        self.copies.remove(&target);
+        self.converted.remove(&target);
+        let released = self.targets.remove(&target).is_some();
+        if released {
+            self.released.push(target);
+        }
+        released
+    }
+}
+
+impl SkyboxGpuConversion {
+    /// Queues a copy of `source`, a texture `N` wide by `6N` high with the faces stacked as
+    /// described in the [crate] docs, into a new 6 layer array texture, and returns the handle of
+    /// the new texture. Returns an error if the source isn't in `images`, can't be split into 6
+    /// square faces, or wasn't created with [`TextureUsages::COPY_SRC`], which the GPU needs to
+    /// copy from it. Only the top mip level is copied.
+    pub fn make_array(
+        &self,
+        source: &Handle<Image>,
+        images: &Assets<Image>,
+    ) -> Result<Handle<Image>, SkyboxError> {
+        let image = images.get(source).ok_or(SkyboxError::TextureNotLoaded)?;
+        let descriptor = &image.texture_descriptor;
+        // Adding the usage here would recreate the texture on the GPU, losing what was drawn
+        // into it.
+        if !descriptor.usage.contains(TextureUsages::COPY_SRC) {
+            return Err(SkyboxError::NotCopySource);
+        }
+        let size = descriptor.size;
+        if size.height % 6 != 0 {
+            return Err(SkyboxError::NotDivisibleBySix {
+                height: size.height,
+            });
+        }
+        let face_size = (size.width, size.height / 6);
+        if face_size.0 != face_size.1 {
+            return Err(SkyboxError::WrongAspectRatio {
+                image_size: (size.width, size.height),
+                expected: (1, 6),
+            });
+        }
+        let (block_width, block_height) = descriptor.format.describe().block_dimensions;
+        if face_size.0 % block_width as u32 != 0 || face_size.1 % block_height as u32 != 0 {
+            return Err(SkyboxError::FaceNotBlockAligned {
+                face_size,
+                block_size: (block_width as u32, block_height as u32),
+            });
+        }
+        let target_descriptor = TextureDescriptor {
             label: None,
-            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            size: Extent3d {
+                width: face_size.0,
+                height: face_size.1,
+                depth_or_array_layers: 6,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: descriptor.format,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::COPY_SRC,
+        };
+
+        let target = Handle::weak(HandleId::random::<Image>());
+        let mut state = self.state.write().unwrap();
+        state.sources.insert(target.id, source.clone_weak());
+        state.targets.insert(target.id, target_descriptor);
+        state.copies.insert(target.id);
+        Ok(target)
+    }
+
+    /// Copies the source of `texture`, a handle returned by [`make_array`](Self::make_array),
+    /// into it again in the next frame. Returns `false` if `texture` isn't a converted texture.
+    pub fn refresh(&self, texture: &Handle<Image>) -> bool {
+        let mut state = self.state.write().unwrap();
+        if !state.targets.contains_key(&texture.id) {
+            return false;
+        }
+        state.copies.insert(texture.id);
+        true
+    }
+
+    /// Frees `texture`, a handle returned by [`make_array`](Self::make_array). Materials still
+    /// using it wait for it like for a texture which hasn't loaded. Returns `false` if `texture`
+    /// isn't a converted texture.
+    pub fn remove(&self, texture: &Handle<Image>) -> bool {
+        self.state.write().unwrap().release(texture.id)
+    }
+
+    /// Checks whether `texture` was returned by [`make_array`](Self::make_array): `None` if it
+    /// wasn't, and otherwise whether its source has been copied into it yet.
+    pub fn is_converted(&self, texture: &Handle<Image>) -> Option<bool> {
+        let state = self.state.read().unwrap();
+        state
+            .targets
+            .contains_key(&texture.id)
+            .then(|| state.converted.contains(&texture.id))
+    }
+}
+
+/// System to free the textures converted by [`SkyboxGpuConversion`] whose sources were removed.
+fn release_skybox_gpu_targets(
+    conversion: Res<SkyboxGpuConversion>,
+    mut events: EventReader<AssetEvent<Image>>,
+) {
+    let removed: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Removed { handle } => Some(handle.id),
+            _ => None,
+        })
+        .collect();
+    if removed.is_empty() {
+        return;
+    }
+    let mut state = conversion.state.write().unwrap();
+    let targets: Vec<_> = state
+        .sources
+        .iter()
+        .filter(|(_, source)| removed.contains(&source.id))
+        .map(|(target, _)| *target)
+        .collect();
+    for target in targets {
+        state.release(target);
+    }
+}
+
+/// Render world system which creates the textures for [`SkyboxGpuConversion`] once their sources
+/// are on the GPU, and frees the ones which were removed. This runs in the queue stage, after the
+/// images have been prepared. The textures are filled by [`SkyboxGpuConversionNode`].
+fn update_skybox_gpu_targets(
+    conversion: Res<SkyboxGpuConversion>,
+    mut gpu_images: ResMut<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+) {
+    let mut state = conversion.state.write().unwrap();
+    let GpuConversionState {
+        sources,
+        targets,
+        released,
+        ..
+    } = &mut *state;
+    for target in released.drain(..) {
+        gpu_images.remove(&Handle::weak(target));
+    }
+    for (id, descriptor) in targets.iter() {
+        let target = Handle::weak(*id);
+        if gpu_images.contains_key(&target) {
+            continue;
+        }
+        let sampler = match sources.get(id).and_then(|source| gpu_images.get(source)) {
+            Some(source) => source.sampler.clone(),
+            None => continue,
+        };
+        let texture = render_device.create_texture(&TextureDescriptor {
+            label: Some("skybox_gpu_converted_texture"),
+            ..descriptor.clone()
+        });
+        let texture_view = texture.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        gpu_images.insert(
+            target,
+            GpuImage {
+                texture,
+                texture_view,
+                sampler,
+            },
+        );
+    }
+}
+
+/// Name of the render graph node which copies the textures queued in [`SkyboxGpuConversion`]. It
+/// runs before the main pass; add an edge to it from any node which renders into a source texture.
+pub const SKYBOX_GPU_CONVERSION_NODE: &str = "skybox_gpu_conversion";
+
+/// Render graph node which records the copies requested from [`SkyboxGpuConversion`] whose
+/// textures are on the GPU.
+struct SkyboxGpuConversionNode;
+
+impl Node for SkyboxGpuConversionNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let conversion = world.get_resource::<SkyboxGpuConversion>().unwrap();
+        let gpu_images = world.get_resource::<RenderAssets<Image>>().unwrap();
+        let mut state = conversion.state.write().unwrap();
+        let GpuConversionState {
+            sources,
+            targets,
+            copies,
+            converted,
+            ..
+        } = &mut *state;
+        copies.retain(|id| {
+            let source = sources.get(id).and_then(|source| gpu_images.get(source));
+            let target = gpu_images.get(&Handle::weak(*id));
+            let (source, target, descriptor) = match (source, target, targets.get(id)) {
+                (Some(source), Some(target), Some(descriptor)) => (source, target, descriptor),
+                _ => return true,
+            };
+            let face_size = descriptor.size.width;
+            for layer in 0..6 {
+                render_context.command_encoder.copy_texture_to_texture(
+                    ImageCopyTexture {
+                        texture: &source.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: layer * face_size,
+                            z: 0,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    ImageCopyTexture {
+                        texture: &target.texture,
+                        mip_level: 0,
+                        origin: Origin3d {
+                            x: 0,
+                            y: 0,
+                            z: layer,
+                        },
+                        aspect: TextureAspect::All,
+                    },
+                    Extent3d {
+                        depth_or_array_layers: 1,
+                        ..descriptor.size
+                    },
+                );
+            }
+            converted.insert(*id);
+            false
+        });
+        Ok(())
+    }
+}
+
+/// Render world resource with the cube textures copied for materials which use
+/// [`SkyboxMaterial::cube_sampling`], by texture and frame. Materials are prepared again whenever
+/// they change, which for animated materials is every frame, so the copies are kept and reused
+/// rather than made on every prepare.
+#[derive(Default)]
+struct SkyboxCubeTextures(HashMap<(HandleId, u32), (TextureId, TextureView)>);
+
+impl SkyboxCubeTextures {
+    /// Returns a cube view of `frame` of `texture`, copying it the first time it's needed, and
+    /// again if the texture has been recreated since.
+    fn get_or_copy(
+        &mut self,
+        render_device: &RenderDevice,
+        render_queue: &RenderQueue,
+        texture: HandleId,
+        gpu_image: &GpuImage,
+        descriptor: &TextureDescriptor,
+        frame: u32,
+    ) -> TextureView {
+        let frame = frame.min((descriptor.size.depth_or_array_layers / 6).max(1) - 1);
+        let source = gpu_image.texture.id();
+        match self.0.get(&(texture, frame)) {
+            Some((copied_from, view)) if *copied_from == source => view.clone(),
+            _ => {
+                let view = copy_to_cube_texture(
+                    render_device,
+                    render_queue,
+                    &gpu_image.texture,
+                    descriptor,
+                    frame,
+                );
+                self.0.insert((texture, frame), (source, view.clone()));
+                view
+            }
+        }
+    }
+}
+
+/// Render world system which frees the cube textures in [`SkyboxCubeTextures`] whose source
+/// texture is gone or has been recreated.
+fn free_skybox_cube_textures(
+    mut cube_textures: ResMut<SkyboxCubeTextures>,
+    gpu_images: Res<RenderAssets<Image>>,
+) {
+    cube_textures.0.retain(|(texture, _), (source, _)| {
+        gpu_images
+            .get(&Handle::weak(*texture))
+            .map_or(false, |gpu_image| gpu_image.texture.id() == *source)
+    });
+}
+
+/// Copies the 6 faces of `frame` of a skybox array texture into a new cube texture, and returns a
+/// cube view of it. The faces swap places along Z, since the GPU's cubemap convention is mirrored
+/// along Z relative to the skybox's; the shader flips Z when sampling to match.
+fn copy_to_cube_texture(
+    render_device: &RenderDevice,
+    render_queue: &RenderQueue,
+    source: &Texture,
+    descriptor: &TextureDescriptor,
+    frame: u32,
+) -> TextureView {
+    let size = Extent3d {
+        width: descriptor.size.width,
+        height: descriptor.size.height,
+        depth_or_array_layers: 6,
+    };
+    let cube = render_device.create_texture(&TextureDescriptor {
+        label: Some("skybox_cube_texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format: descriptor.format,
+        usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+    });
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("skybox_cube_copy"),
+    });
+    for (layer, face) in SKYBOX_FACE_ORDER.iter().enumerate() {
+        let source_layer = match face {
+            SkyboxFace::PosZ => SkyboxFace::NegZ.layer_index(),
+            SkyboxFace::NegZ => SkyboxFace::PosZ.layer_index(),
+            _ => layer,
+        };
+        encoder.copy_texture_to_texture(
+            ImageCopyTexture {
+                texture: source,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: frame * 6 + source_layer as u32,
+                },
+                aspect: TextureAspect::All,
+            },
+            ImageCopyTexture {
+                texture: &cube,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: layer as u32,
+                },
+                aspect: TextureAspect::All,
+            },
+            Extent3d {
+                depth_or_array_layers: 1,
+                ..size
+            },
+        );
+    }
+    render_queue.submit([encoder.finish()]);
+    cube.create_view(&TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..Default::default()
+    })
+}
+
+/// Depth comparison which lets the skybox through wherever nothing nearer has been drawn. See
+/// [`SkyboxDepthConvention::depth_compare`].
+fn skybox_depth_compare(standard_depth: bool) -> CompareFunction {
+    if standard_depth {
+        SkyboxDepthConvention::Standard.depth_compare()
+    } else {
+        SkyboxDepthConvention::ReverseZ.depth_compare()
+    }
+}
+
+/// Name of the render graph node which draws skyboxes at half resolution. See
+/// [`SkyboxPlugin::half_resolution`].
+pub const SKYBOX_HALF_RES_NODE: &str = "skybox_half_res_pass";
+
+/// Sets up drawing skyboxes at half resolution in the render app. This needs the render graph and
+/// `MeshPipeline` of Bevy's default plugins, which must be added before the [`SkyboxPlugin`].
+fn add_skybox_half_res_pass(render_app: &mut App) {
+    render_app
+        .init_resource::<DrawFunctions<SkyboxHalfResItem>>()
+        .init_resource::<SkyboxUpsamplePipeline>()
+        .init_resource::<SpecializedPipelines<SkyboxUpsamplePipeline>>()
+        .add_render_command::<SkyboxHalfResItem, DrawSkyboxHalfRes>()
+        .add_render_command::<Opaque3d, DrawSkyboxUpsample>()
+        .add_system_to_stage(RenderStage::Extract, extract_skybox_half_res_phases)
+        .add_system_to_stage(RenderStage::Prepare, prepare_skybox_half_res_targets)
+        .add_system_to_stage(RenderStage::Queue, queue_skybox_half_res)
+        .add_system_to_stage(
+            RenderStage::PhaseSort,
+            sort_phase_system::<SkyboxHalfResItem>,
+        );
+
+    let node = SkyboxHalfResNode::new(&mut render_app.world);
+    let mut graph = render_app.world.get_resource_mut::<RenderGraph>().unwrap();
+    let draw_3d_graph = graph.get_sub_graph_mut(draw_3d_graph::NAME).unwrap();
+    draw_3d_graph.add_node(SKYBOX_HALF_RES_NODE, node);
+    draw_3d_graph
+        .add_node_edge(SKYBOX_HALF_RES_NODE, draw_3d_graph::node::MAIN_PASS)
+        .unwrap();
+    draw_3d_graph
+        .add_slot_edge(
+            draw_3d_graph.input_node().unwrap().id,
+            draw_3d_graph::input::VIEW_ENTITY,
+            SKYBOX_HALF_RES_NODE,
+            SkyboxHalfResNode::IN_VIEW,
+        )
+        .unwrap();
+}
+
+/// A skybox drawn into the half resolution texture of a view.
+struct SkyboxHalfResItem {
+    /// Opaque skyboxes are drawn first, then blended ones, each in the order of their entities.
+    sort_key: (bool, Entity),
+    entity: Entity,
+    pipeline: CachedPipelineId,
+    draw_function: DrawFunctionId,
+}
+
+impl PhaseItem for SkyboxHalfResItem {
+    type SortKey = (bool, Entity);
+
+    fn sort_key(&self) -> Self::SortKey {
+        self.sort_key
+    }
+
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+}
+
+impl EntityPhaseItem for SkyboxHalfResItem {
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl CachedPipelinePhaseItem for SkyboxHalfResItem {
+    fn cached_pipeline(&self) -> CachedPipelineId {
+        self.pipeline
+    }
+}
+
+/// Draws a skybox with its material, like Bevy draws any other material.
+type DrawSkyboxHalfRes = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMaterialBindGroup<SkyboxMaterial, 1>,
+    SetMeshBindGroup<2>,
+    DrawMesh,
+);
+
+/// Draws a skybox mesh which stretches the view's half resolution texture over the view.
+type DrawSkyboxUpsample = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetSkyboxUpsampleBindGroup<1>,
+    SetMeshBindGroup<2>,
+    DrawMesh,
+);
+
+/// Binds the [`SkyboxHalfResTarget`] of the view being drawn.
+struct SetSkyboxUpsampleBindGroup<const I: usize>;
+
+impl<const I: usize> EntityRenderCommand for SetSkyboxUpsampleBindGroup<I> {
+    type Param = SQuery<Read<SkyboxHalfResTarget>>;
+
+    fn render<'w>(
+        view: Entity,
+        _item: Entity,
+        targets: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let target = targets.get(view).unwrap();
+        pass.set_bind_group(I, &target.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+/// The half resolution texture skyboxes are drawn into for a view, and the bind group for
+/// stretching it over the view.
+#[derive(Component)]
+struct SkyboxHalfResTarget {
+    texture: CachedTexture,
+    bind_group: BindGroup,
+}
+
+/// Pipeline which stretches the half resolution texture over the view. It's drawn with the skybox
+/// vertex shader, so it ends up at the same depth a skybox would.
+struct SkyboxUpsamplePipeline {
+    mesh_pipeline: MeshPipeline,
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    standard_depth: bool,
+}
+
+impl FromWorld for SkyboxUpsamplePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.get_resource::<RenderDevice>().unwrap();
+        let layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("skybox_upsample_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
         });
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            mesh_pipeline: world.get_resource::<MeshPipeline>().unwrap().clone(),
+            layout,
+            sampler,
+            standard_depth: world
+                .get_resource::<SkyboxDepthConvention>()
+                .map_or(false, |convention| {
+                    *convention == SkyboxDepthConvention::Standard
+                }),
+        }
+    }
+}
 
-        let (base_color_texture_view, base_color_sampler) = if let Some(result) = material_pipeline
-            .mesh_pipeline
-            .get_image_texture(gpu_images, &material.texture)
-        {
-            result
-        } else {
-            return Err(PrepareAssetError::RetryNextUpdate(material));
-        };
+impl SpecializedPipeline for SkyboxUpsamplePipeline {
+    type Key = MeshPipelineKey;
 
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.mesh_pipeline.specialize(key);
+        descriptor.label = Some("skybox_upsample_pipeline".into());
+        descriptor.vertex.shader = SKYBOX_VERTEX_SHADER_HANDLE.typed::<Shader>();
+        descriptor.vertex.entry_point = "main".into();
+        if self.standard_depth {
+            descriptor
+                .vertex
+                .shader_defs
+                .push("SKYBOX_STANDARD_DEPTH".to_string());
+        }
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader = SKYBOX_UPSAMPLE_FRAGMENT_SHADER_HANDLE.typed::<Shader>();
+        fragment.entry_point = "main".into();
+        for target in fragment.targets.iter_mut() {
+            target.blend = None;
+        }
+        descriptor.layout = Some(vec![
+            self.mesh_pipeline.view_layout.clone(),
+            self.layout.clone(),
+            self.mesh_pipeline.mesh_layout.clone(),
+        ]);
+        descriptor.primitive.cull_mode = Some(Face::Front);
+        if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+            depth_stencil.depth_write_enabled = false;
+            depth_stencil.depth_compare = skybox_depth_compare(self.standard_depth);
+        }
+        descriptor
+    }
+}
+
+/// Adds the half resolution skybox phase to the 3d camera.
+fn extract_skybox_half_res_phases(mut commands: Commands, active_cameras: Res<ActiveCameras>) {
+    if let Some(entity) = active_cameras
+        .get(CameraPlugin::CAMERA_3D)
+        .and_then(|camera| camera.entity)
+    {
+        commands
+            .get_or_spawn(entity)
+            .insert(RenderPhase::<SkyboxHalfResItem>::default());
+    }
+}
+
+/// Creates the half resolution texture of each view with the half resolution skybox phase.
+fn prepare_skybox_half_res_targets(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    upsample_pipeline: Res<SkyboxUpsamplePipeline>,
+    views: Query<(Entity, &ExtractedView), With<RenderPhase<SkyboxHalfResItem>>>,
+) {
+    for (entity, view) in views.iter() {
+        let texture = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("skybox_half_res_texture"),
+                size: Extent3d {
+                    width: ((view.width + 1) / 2).max(1),
+                    height: ((view.height + 1) / 2).max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::bevy_default(),
+                usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            },
+        );
         let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("skybox_upsample_bind_group"),
+            layout: &upsample_pipeline.layout,
             entries: &[
                 BindGroupEntry {
                     binding: 0,
-                    resource: buffer.as_entire_binding(),
+                    resource: BindingResource::TextureView(&texture.default_view),
                 },
                 BindGroupEntry {
                     binding: 1,
-                    resource: BindingResource::TextureView(base_color_texture_view),
-                },
-                BindGroupEntry {
-                    binding: 2,
-                    resource: BindingResource::Sampler(base_color_sampler),
+                    resource: BindingResource::Sampler(&upsample_pipeline.sampler),
                 },
             ],
-            label: None,
-            layout: &material_pipeline.material_layout,
         });
-
-        Ok(GpuSkyboxMaterial {
-            _buffer: buffer,
+        commands.entity(entity).insert(SkyboxHalfResTarget {
+            texture,
             bind_group,
-        })
+        });
     }
 }
 
-impl SpecializedMaterial for SkyboxMaterial {
-    type Key = ();
-
-    fn key(_: &<SkyboxMaterial as RenderAsset>::PreparedAsset) -> Self::Key {}
+/// Queues the visible skyboxes of each view into its half resolution phase, and a single draw
+/// which stretches the result over the view into its opaque phase.
+#[allow(clippy::too_many_arguments)]
+fn queue_skybox_half_res(
+    half_res_draw_functions: Res<DrawFunctions<SkyboxHalfResItem>>,
+    opaque_draw_functions: Res<DrawFunctions<Opaque3d>>,
+    material_pipeline: Res<MaterialPipeline<SkyboxMaterial>>,
+    mut material_pipelines: ResMut<SpecializedPipelines<MaterialPipeline<SkyboxMaterial>>>,
+    upsample_pipeline: Res<SkyboxUpsamplePipeline>,
+    mut upsample_pipelines: ResMut<SpecializedPipelines<SkyboxUpsamplePipeline>>,
+    mut pipeline_cache: ResMut<RenderPipelineCache>,
+    msaa: Res<Msaa>,
+    render_meshes: Res<RenderAssets<Mesh>>,
+    render_materials: Res<RenderAssets<SkyboxMaterial>>,
+    skyboxes: Query<(&Handle<SkyboxMaterial>, &Handle<Mesh>)>,
+    mut views: Query<(
+        &VisibleEntities,
+        &mut RenderPhase<SkyboxHalfResItem>,
+        &mut RenderPhase<Opaque3d>,
+    )>,
+) {
+    let draw_half_res = half_res_draw_functions
+        .read()
+        .get_id::<DrawSkyboxHalfRes>()
+        .unwrap();
+    let draw_upsample = opaque_draw_functions
+        .read()
+        .get_id::<DrawSkyboxUpsample>()
+        .unwrap();
+    for (visible_entities, mut half_res_phase, mut opaque_phase) in views.iter_mut() {
+        let mut upsample_entity = None;
+        for &entity in &visible_entities.entities {
+            let (material, mesh) = match skyboxes.get(entity) {
+                Ok((material, mesh)) => (material, mesh),
+                Err(_) => continue,
+            };
+            let material = match render_materials.get(material) {
+                Some(material) => material,
+                None => continue,
+            };
+            let topology = match render_meshes.get(mesh) {
+                Some(mesh) => MeshPipelineKey::from_primitive_topology(mesh.primitive_topology),
+                None => continue,
+            };
+            // The half resolution texture isn't multisampled.
+            let key = SkyboxMaterialKey {
+                half_res: true,
+                ..material.key.clone()
+            };
+            let pipeline = material_pipelines.specialize(
+                &mut pipeline_cache,
+                &material_pipeline,
+                (
+                    MeshPipelineKey::from_msaa_samples(1) | topology,
+                    key.clone(),
+                ),
+            );
+            half_res_phase.add(SkyboxHalfResItem {
+                sort_key: (key.blend_mode != SkyboxBlendMode::Opaque, entity),
+                entity,
+                pipeline,
+                draw_function: draw_half_res,
+            });
+            upsample_entity.get_or_insert((entity, topology));
+        }
 
-    fn specialize(_: Self::Key, descriptor: &mut RenderPipelineDescriptor) {
-        descriptor.vertex.entry_point = "main".into();
-        descriptor.fragment.as_mut().unwrap().entry_point = "main".into();
-        descriptor.primitive.cull_mode = Some(Face::Front);
+        // Any skybox's mesh and transform will do for the upsample, since it covers the whole view.
+        if let Some((entity, topology)) = upsample_entity {
+            let pipeline = upsample_pipelines.specialize(
+                &mut pipeline_cache,
+                &upsample_pipeline,
+                MeshPipelineKey::from_msaa_samples(msaa.samples) | topology,
+            );
+            opaque_phase.add(Opaque3d {
+                // Opaque meshes are drawn front to back, so this puts the skybox last.
+                distance: f32::MAX,
+                pipeline,
+                entity,
+                draw_function: draw_upsample,
+            });
+        }
     }
+}
 
-    fn vertex_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
-        Some(SKYBOX_VERTEX_SHADER_HANDLE.typed::<Shader>())
+/// Render graph node which draws the half resolution skybox phase of a view into its
+/// [`SkyboxHalfResTarget`].
+struct SkyboxHalfResNode {
+    query: QueryState<(
+        &'static RenderPhase<SkyboxHalfResItem>,
+        &'static SkyboxHalfResTarget,
+    )>,
+}
+
+impl SkyboxHalfResNode {
+    const IN_VIEW: &'static str = "view";
+
+    fn new(world: &mut World) -> Self {
+        Self {
+            query: QueryState::new(world),
+        }
     }
+}
 
-    fn fragment_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
-        Some(SKYBOX_FRAGMENT_SHADER_HANDLE.typed::<Shader>())
+impl Node for SkyboxHalfResNode {
+    fn input(&self) -> Vec<SlotInfo> {
+        vec![SlotInfo::new(Self::IN_VIEW, SlotType::Entity)]
     }
 
-    fn bind_group(render_asset: &<Self as RenderAsset>::PreparedAsset) -> &BindGroup {
-        &render_asset.bind_group
+    fn update(&mut self, world: &mut World) {
+        self.query.update_archetypes(world);
     }
 
-    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
-        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            entries: &[
-                BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: ShaderStages::FRAGMENT,
-                    ty: BindingType::Buffer {
-                        ty: BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: BufferSize::new(Vec4::std140_size_static() as u64),
-                    },
-                    count: None,
-                },
-                // Texture
-                BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: ShaderStages::FRAGMENT,
-                    ty: BindingType::Texture {
-                        multisampled: false,
-                        sample_type: TextureSampleType::Float { filterable: true },
-                        view_dimension: TextureViewDimension::D2Array,
-                    },
-                    count: None,
-                },
-                // Texture Sampler
-                BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: ShaderStages::FRAGMENT,
-                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
-                    count: None,
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let view_entity = graph.get_input_entity(Self::IN_VIEW)?;
+        let (phase, target) = match self.query.get_manual(world, view_entity) {
+            Ok(query) => query,
+            // Views other than the 3d camera don't have a half resolution skybox.
+            Err(_) => return Ok(()),
+        };
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+        let pass_descriptor = RenderPassDescriptor {
+            label: Some("skybox_half_res_pass"),
+            color_attachments: &[RenderPassColorAttachment {
+                view: &target.texture.default_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::BLACK.into()),
+                    store: true,
                 },
-            ],
-            label: None,
-        })
+            }],
+            depth_stencil_attachment: None,
+        };
+        let draw_functions = world
+            .get_resource::<DrawFunctions<SkyboxHalfResItem>>()
+            .unwrap();
+        let render_pass = render_context
+            .command_encoder
+            .begin_render_pass(&pass_descriptor);
+        let mut draw_functions = draw_functions.write();
+        let mut tracked_pass = TrackedRenderPass::new(render_pass);
+        for item in &phase.items {
+            let draw_function = draw_functions.get_mut(item.draw_function).unwrap();
+            draw_function.draw(world, &mut tracked_pass, view_entity, item);
+        }
+        Ok(())
     }
 }
 
-/// Resource to help with converting skyboxes stored as vertically stacked images as described in
-/// the [crate] documentation into array textures in the correct format for use in a
-/// [`SkyboxMaterial`].
-///
-/// The [`SkyboxPlugin`] will add this resource and install an associated system which handles the
-/// actual texture conversion. Conversion is performed using
-/// [`Texture::reinterpret_stacked_2d_as_array`]. If you prefer, you are free to handle converting
-/// textures yourself, or use a texture format + loader which can load array textures directly.
-#[derive(Default)]
-pub struct SkyboxTextureConversion {
-    /// List of texture handles that should be skyboxes.
-    handles: Vec<Handle<Image>>,
+/// Draws a skybox as a single triangle covering the view. See [`SkyboxPlugin::fullscreen_triangle`].
+type DrawSkyboxFullscreen = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMaterialBindGroup<SkyboxMaterial, 1>,
+    DrawFullscreenTriangle,
+);
+
+/// Draws the 3 vertices of the triangle covering the view, which the vertex shader generates
+/// without any vertex buffers.
+struct DrawFullscreenTriangle;
+
+impl EntityRenderCommand for DrawFullscreenTriangle {
+    type Param = ();
+
+    fn render<'w>(
+        _view: Entity,
+        _item: Entity,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.draw(0..3, 0..1);
+        RenderCommandResult::Success
+    }
 }
 
-impl SkyboxTextureConversion {
-    /// Takes a handle to a texture whose dimensions are `N` wide by `6*N` high, waits for it to load,
-    /// and then reinterprets that texture as an array of 6 textures suitable or a skybox. This is
-    /// useful if your skybox texture is not in a format that has layers. This should only be done
-    /// once per testure, and will panic if the texture has already be reinterpreted.
-    pub fn make_array(&mut self, handle: Handle<Image>) {
-        self.handles.push(handle);
+/// Pipeline for drawing skyboxes as a single triangle covering the view. It's the skybox material's
+/// pipeline without the mesh: the vertex shader is swapped out and the mesh's vertex buffer and
+/// bind group are left out.
+struct SkyboxFullscreenPipeline {
+    mesh_pipeline: MeshPipeline,
+    material_layout: BindGroupLayout,
+}
+
+impl FromWorld for SkyboxFullscreenPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let material_pipeline = world
+            .get_resource::<MaterialPipeline<SkyboxMaterial>>()
+            .unwrap();
+        Self {
+            mesh_pipeline: material_pipeline.mesh_pipeline.clone(),
+            material_layout: material_pipeline.material_layout.clone(),
+        }
     }
 }
 
-/// System to handle reinterpreting an Nx6N vertical texture stack as an array of textures suitable
-/// for a skybox.
-fn convert_skyboxes(
-    mut conversions: ResMut<SkyboxTextureConversion>,
-    mut textures: ResMut<Assets<Image>>,
+impl SpecializedPipeline for SkyboxFullscreenPipeline {
+    type Key = (MeshPipelineKey, SkyboxMaterialKey);
+
+    fn specialize(&self, (mesh_key, material_key): Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = self.mesh_pipeline.specialize(mesh_key);
+        descriptor.label = Some("skybox_fullscreen_pipeline".into());
+        descriptor.vertex.shader = SKYBOX_FULLSCREEN_VERTEX_SHADER_HANDLE.typed::<Shader>();
+        descriptor.vertex.buffers.clear();
+        descriptor.fragment.as_mut().unwrap().shader =
+            SKYBOX_FRAGMENT_SHADER_HANDLE.typed::<Shader>();
+        descriptor.layout = Some(vec![
+            self.mesh_pipeline.view_layout.clone(),
+            self.material_layout.clone(),
+        ]);
+        <SkyboxMaterial as SpecializedMaterial>::specialize(material_key, &mut descriptor);
+        // The triangle is seen from the front, unlike the inside of the cube.
+        descriptor.primitive.cull_mode = None;
+        descriptor
+    }
+}
+
+/// Queues the visible skyboxes of each view to be drawn as a triangle covering the view, opaque
+/// ones into the opaque phase and blended ones into the transparent phase, behind everything else.
+#[allow(clippy::too_many_arguments)]
+fn queue_skybox_fullscreen(
+    opaque_draw_functions: Res<DrawFunctions<Opaque3d>>,
+    transparent_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    fullscreen_pipeline: Res<SkyboxFullscreenPipeline>,
+    mut pipelines: ResMut<SpecializedPipelines<SkyboxFullscreenPipeline>>,
+    mut pipeline_cache: ResMut<RenderPipelineCache>,
+    msaa: Res<Msaa>,
+    render_materials: Res<RenderAssets<SkyboxMaterial>>,
+    skyboxes: Query<&Handle<SkyboxMaterial>>,
+    mut views: Query<(
+        &VisibleEntities,
+        &mut RenderPhase<Opaque3d>,
+        &mut RenderPhase<Transparent3d>,
+    )>,
 ) {
-    let mut index = 0;
-    while let Some(handle) = conversions.handles.get(index) {
-        // Check each texture in the pending queue to see if it is loaded yet.
-        let (handle, texture) = match textures.get_mut(handle) {
-            // If it's loaded, take it out of the queue.
-            Some(texture) => (conversions.handles.remove(index), texture),
-            None => {
-                index += 1;
-                continue;
+    let draw_opaque = opaque_draw_functions
+        .read()
+        .get_id::<DrawSkyboxFullscreen>()
+        .unwrap();
+    let draw_transparent = transparent_draw_functions
+        .read()
+        .get_id::<DrawSkyboxFullscreen>()
+        .unwrap();
+    let mesh_key = MeshPipelineKey::from_msaa_samples(msaa.samples)
+        | MeshPipelineKey::from_primitive_topology(PrimitiveTopology::TriangleList);
+    for (visible_entities, mut opaque_phase, mut transparent_phase) in views.iter_mut() {
+        for &entity in &visible_entities.entities {
+            let material = match skyboxes
+                .get(entity)
+                .ok()
+                .and_then(|material| render_materials.get(material))
+            {
+                Some(material) => material,
+                None => continue,
+            };
+            let key = SkyboxMaterial::key(material);
+            if key.blend_mode == SkyboxBlendMode::Opaque {
+                let pipeline = pipelines.specialize(
+                    &mut pipeline_cache,
+                    &fullscreen_pipeline,
+                    (mesh_key, key),
+                );
+                opaque_phase.add(Opaque3d {
+                    // Opaque meshes are drawn front to back, so this puts the skybox last.
+                    distance: f32::MAX,
+                    pipeline,
+                    entity,
+                    draw_function: draw_opaque,
+                });
+            } else {
+                let pipeline = pipelines.specialize(
+                    &mut pipeline_cache,
+                    &fullscreen_pipeline,
+                    (mesh_key | MeshPipelineKey::TRANSPARENT_MAIN_PASS, key),
+                );
+                transparent_phase.add(Transparent3d {
+                    // Transparent meshes are drawn back to front, so this puts the skybox first.
+                    distance: f32::MIN,
+                    pipeline,
+                    entity,
+                    draw_function: draw_transparent,
+                });
             }
-        };
+        }
+    }
+}
 
-        info!(
-            "Reinterpreting as Skybox Texture {:?}: len: {}",
-            handle,
-            texture.data.len(),
-        );
-        texture.reinterpret_stacked_2d_as_array(6);
+/// Checks whether textures in the given format can always be sampled with filtering.
+///
+/// Without [`WgpuFeatures::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`], wgpu only allows the
+/// guaranteed features of each format. With it, the adapter may allow filtering more formats, such
+/// as 32-bit floats, but the render world doesn't have the adapter to ask, so those formats are
+/// still sampled without filtering. That is valid whatever the adapter supports.
+fn is_filterable(format: TextureFormat) -> bool {
+    format.describe().guaranteed_format_features.filterable
+}
+
+/// Creates a [`TextureViewDimension::Cube`] view of a prepared skybox texture, for binding it as an
+/// environment map in your own render pipelines. Use this from a render world system, getting the
+/// [`GpuImage`] from [`RenderAssets<Image>`] with the handle from
+/// [`SkyboxMaterial::as_environment_map_handle`].
+///
+/// The image must have 6 square layers of the same size, which is what skybox textures already
+/// need.
+pub fn skybox_cube_view(gpu_image: &GpuImage) -> TextureView {
+    gpu_image.texture.create_view(&TextureViewDescriptor {
+        label: Some("skybox_cube_view"),
+        dimension: Some(TextureViewDimension::Cube),
+        array_layer_count: std::num::NonZeroU32::new(6),
+        ..Default::default()
+    })
+}
+
+/// Builds a 6-layer array [`Image`] suitable for a [`SkyboxMaterial`] from the raw bytes of the six
+/// faces. The faces must be given in the layer order described in the [crate] docs (+X, -X, +Y, -Y,
+/// +Z, -Z), and each must be a square `face_size` by `face_size` image in the given `format`.
+///
+/// This is useful for skyboxes which are generated procedurally or decoded at runtime rather than
+/// loaded through the [`AssetServer`]. The returned image is already an array texture, so it does not
+/// need to be passed to [`SkyboxTextureConversion`].
+///
+/// Returns an error if any of the faces doesn't have the number of bytes expected for its size
+/// and format.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::TextureFormat;
+/// # use bevy_skybox_cubemap::{stacked_skybox_image, SkyboxMaterial};
+/// # fn setup(mut images: ResMut<Assets<Image>>, mut skyboxes: ResMut<Assets<SkyboxMaterial>>) {
+/// let face = vec![128u8; 16 * 16 * 4];
+/// let faces = [&face[..]; 6];
+/// let image = stacked_skybox_image(faces, 16, TextureFormat::Rgba8UnormSrgb).unwrap();
+/// let skybox = skyboxes.add(SkyboxMaterial::from_texture(images.add(image)));
+/// # }
+/// ```
+pub fn stacked_skybox_image(
+    faces: [&[u8]; 6],
+    face_size: u32,
+    format: TextureFormat,
+) -> Result<Image, SkyboxError> {
+    let data = conversion::stack_faces(faces, face_byte_len(face_size, format))?;
+    Ok(Image::new(
+        Extent3d {
+            width: face_size,
+            height: face_size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        data,
+        format,
+    ))
+}
+
+/// Builder for a skybox texture assembled from a separate image for each face, such as a skybox
+/// exported as 6 files, with each face optionally rotated. The faces can be any mix of loaded and
+/// generated images and regions of atlas images, as long as they are all square, the same size,
+/// and the same format. Once the texture is assembled, the faces are no longer kept loaded.
+///
+/// [`finish`](Self::finish) queues the faces in [`SkyboxTextureConversion`] and returns the handle
+/// of the texture they will be assembled into, which can be used in a [`SkyboxMaterial`] right
+/// away. Until all of the faces have loaded, it is a plain white placeholder.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_skybox_cubemap::{
+/// #     FaceRotation, SkyboxAssembler, SkyboxBundle, SkyboxFace, SkyboxMaterial,
+/// #     SkyboxTextureConversion,
+/// # };
+/// fn setup(
+///     mut commands: Commands,
+///     asset_server: Res<AssetServer>,
+///     mut images: ResMut<Assets<Image>>,
+///     mut skyboxes: ResMut<Assets<SkyboxMaterial>>,
+///     mut conversion: ResMut<SkyboxTextureConversion>,
+/// ) {
+///     let texture = SkyboxAssembler::new()
+///         .face(SkyboxFace::PosX, asset_server.load("sky/right.png"))
+///         .face(SkyboxFace::NegX, asset_server.load("sky/left.png"))
+///         .face(SkyboxFace::PosY, asset_server.load("sky/up.png"))
+///         .face(SkyboxFace::NegY, asset_server.load("sky/down.png"))
+///         .face(SkyboxFace::PosZ, asset_server.load("sky/back.png"))
+///         .face(SkyboxFace::NegZ, asset_server.load("sky/front.png"))
+///         .rotate(SkyboxFace::NegY, FaceRotation::Cw90)
+///         .finish(&mut conversion, &mut images)
+///         .unwrap();
+///     commands.spawn_bundle(SkyboxBundle::new(
+///         skyboxes.add(SkyboxMaterial::from_texture(texture)),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SkyboxAssembler {
+    faces: [Option<Handle<Image>>; 6],
+    regions: [Option<Rect<u32>>; 6],
+    rotations: [FaceRotation; 6],
+}
+
+impl SkyboxAssembler {
+    /// Creates an assembler with no faces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `image` for `face`, replacing any image given for it before.
+    pub fn face(mut self, face: SkyboxFace, image: Handle<Image>) -> Self {
+        self.faces[face.layer_index()] = Some(image);
+        self.regions[face.layer_index()] = None;
+        self
+    }
+
+    /// Uses the pixels of `image` inside `rect` for `face`, replacing any image given for it
+    /// before, e.g. for a face packed into an atlas with other images. `left` and `top` are
+    /// inclusive and `right` and `bottom` exclusive, like in [`AtlasLayout`]. Faces taken from part
+    /// of an image don't work for block-compressed textures.
+    pub fn face_region(mut self, face: SkyboxFace, image: Handle<Image>, rect: Rect<u32>) -> Self {
+        self.faces[face.layer_index()] = Some(image);
+        self.regions[face.layer_index()] = Some(rect);
+        self
+    }
+
+    /// Takes every face from `atlas`, at the rectangles of `layout`, like
+    /// [`SkyboxTextureConversion::make_array_from_atlas`]. Faces can still be replaced or rotated
+    /// individually afterwards.
+    pub fn atlas(mut self, atlas: Handle<Image>, layout: AtlasLayout) -> Self {
+        for (face, rect) in SKYBOX_FACE_ORDER.into_iter().zip(layout.0) {
+            self = self.face_region(face, atlas.clone(), rect);
+        }
+        self
+    }
+
+    /// Rotates `face` clockwise by `rotation` when assembling the texture.
+    pub fn rotate(mut self, face: SkyboxFace, rotation: FaceRotation) -> Self {
+        self.rotations[face.layer_index()] = rotation;
+        self
+    }
+
+    /// Queues the texture to be assembled once all of its faces have loaded, and returns its
+    /// handle. Returns an error if a face is missing.
+    ///
+    /// Problems with the faces themselves, such as faces of different sizes or formats, are
+    /// logged as errors once they have loaded, like other conversion errors, and leave the
+    /// placeholder in place. Rotating faces doesn't work for block-compressed textures.
+    pub fn finish(
+        self,
+        conversion: &mut SkyboxTextureConversion,
+        images: &mut Assets<Image>,
+    ) -> Result<Handle<Image>, SkyboxError> {
+        let mut faces = Vec::with_capacity(6);
+        for (face, image) in SKYBOX_FACE_ORDER.into_iter().zip(self.faces) {
+            faces.push(image.ok_or(SkyboxError::MissingFace(face))?);
+        }
+        let faces: [Handle<Image>; 6] = faces.try_into().unwrap();
+        let placeholder = images.add(Image::new_fill(
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 6,
+            },
+            TextureDimension::D2,
+            &[255, 255, 255, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        ));
+        conversion.queue(PendingConversion {
+            handle: placeholder.clone_weak(),
+            method: ConversionMethod::Assemble {
+                faces,
+                regions: self.regions,
+                rotations: self.rotations,
+            },
+            priority: 0,
+        });
+        Ok(placeholder)
+    }
+}
+
+/// Copies the pixels of `image` inside `rect` into a new image, for the face of a
+/// [`SkyboxAssembler`] with the index `face`.
+fn crop_skybox_face(image: &Image, rect: Rect<u32>, face: usize) -> Result<Image, SkyboxError> {
+    let size = image.texture_descriptor.size;
+    let width = rect.right.saturating_sub(rect.left);
+    let height = rect.bottom.saturating_sub(rect.top);
+    if width == 0 || height == 0 || rect.right > size.width || rect.bottom > size.height {
+        return Err(SkyboxError::AtlasFaceOutOfBounds {
+            face,
+            image_size: (size.width, size.height),
+        });
+    }
+    let format = image.texture_descriptor.format;
+    let data = conversion::copy_region(
+        &image.data,
+        size.width as usize,
+        uncompressed_pixel_size(format)?,
+        rect.left as usize,
+        rect.top as usize,
+        width as usize,
+        height as usize,
+    );
+    let mut cropped = Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        format,
+    );
+    cropped.sampler_descriptor = image.sampler_descriptor.clone();
+    Ok(cropped)
+}
+
+/// Assembles a 6-layer array [`Image`] suitable for a [`SkyboxMaterial`] from a separate image for
+/// each face, given in the layer order described in the [crate] docs, rotating each face clockwise
+/// by the corresponding entry of `rotations`. This is the conversion used by [`SkyboxAssembler`].
+///
+/// Returns an error if the faces aren't square, aren't all the same size and format, or need to be
+/// rotated but are block-compressed.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+/// # use bevy_skybox_cubemap::{assemble_skybox, FaceRotation};
+/// let faces: Vec<Image> = (0..6)
+///     .map(|layer| {
+///         Image::new_fill(
+///             Extent3d { width: 2, height: 2, depth_or_array_layers: 1 },
+///             TextureDimension::D2,
+///             &[layer, 0, 0, 255],
+///             TextureFormat::Rgba8UnormSrgb,
+///         )
+///     })
+///     .collect();
+/// let faces = [&faces[0], &faces[1], &faces[2], &faces[3], &faces[4], &faces[5]];
+/// let array = assemble_skybox(faces, [FaceRotation::None; 6]).unwrap();
+/// assert_eq!(array.texture_descriptor.size.depth_or_array_layers, 6);
+/// assert_eq!(array.data[4 * 4 * 3], 3);
+/// ```
+pub fn assemble_skybox(
+    faces: [&Image; 6],
+    rotations: [FaceRotation; 6],
+) -> Result<Image, SkyboxError> {
+    let descriptor = &faces[0].texture_descriptor;
+    let (size, format) = (descriptor.size, descriptor.format);
+    if size.width != size.height {
+        return Err(SkyboxError::FaceNotSquare {
+            width: size.width,
+            height: size.height,
+        });
+    }
+    for (face, image) in faces.iter().enumerate().skip(1) {
+        let other = &image.texture_descriptor;
+        if other.format != format {
+            return Err(SkyboxError::FaceFormatMismatch {
+                face,
+                expected: format,
+                actual: other.format,
+            });
+        }
+        if (other.size.width, other.size.height) != (size.width, size.height) {
+            return Err(SkyboxError::AtlasFaceSizeMismatch {
+                face,
+                expected: (size.width, size.height),
+                actual: (other.size.width, other.size.height),
+            });
+        }
+    }
+    let mut image = stacked_skybox_image(faces.map(|face| &face.data[..]), size.width, format)?;
+    if rotations
+        .iter()
+        .any(|&rotation| rotation != FaceRotation::None)
+    {
+        let pixel_size = uncompressed_pixel_size(format)?;
+        conversion::rotate_faces(&mut image.data, size.width as usize, pixel_size, rotations);
+    }
+    image.sampler_descriptor = faces[0].sampler_descriptor.clone();
+    Ok(image)
+}
+
+/// Magic bytes at the start of files written by [`cache_converted`]. The last byte is the version of
+/// the file format, so files written by a different version are ignored.
+const CACHE_MAGIC: &[u8; 8] = b"SKYCACH2";
+
+/// Length of the header of files written by [`cache_converted`], before the texture data.
+const CACHE_HEADER_LEN: usize = 59;
+
+/// Address modes which can be stored in cache files, identified by their index.
+const CACHEABLE_ADDRESS_MODES: [AddressMode; 4] = [
+    AddressMode::ClampToEdge,
+    AddressMode::Repeat,
+    AddressMode::MirrorRepeat,
+    AddressMode::ClampToBorder,
+];
+
+/// Filter modes which can be stored in cache files, identified by their index.
+const CACHEABLE_FILTER_MODES: [FilterMode; 2] = [FilterMode::Nearest, FilterMode::Linear];
+
+/// Formats which [`cache_converted`] can write, identified in cache files by their index.
+const CACHEABLE_FORMATS: [TextureFormat; 10] = [
+    TextureFormat::Rgba8Unorm,
+    TextureFormat::Rgba8UnormSrgb,
+    TextureFormat::Bgra8Unorm,
+    TextureFormat::Bgra8UnormSrgb,
+    TextureFormat::Rgba16Float,
+    TextureFormat::Rgba32Float,
+    TextureFormat::Bc1RgbaUnormSrgb,
+    TextureFormat::Bc3RgbaUnormSrgb,
+    TextureFormat::Bc6hRgbUfloat,
+    TextureFormat::Bc7RgbaUnormSrgb,
+];
+
+/// Computes the key identifying a converted skybox texture for [`cache_converted`] and
+/// [`try_load_cached`], from the path of the source image, its modification time, and a
+/// description of how it's converted, such as `"stacked"` or `"grid 3x2"`. Editing the source
+/// image or changing how it's converted changes the key, so stale caches are ignored.
+///
+/// Returns `None` if the source's modification time can't be read, e.g. because it doesn't exist.
+/// Keys are only meant to be compared between runs of the same build of an app; a cache written by
+/// a different build may simply be ignored.
+pub fn skybox_cache_key(source: &std::path::Path, conversion: &str) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+    let modified = std::fs::metadata(source).ok()?.modified().ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    modified.hash(&mut hasher);
+    conversion.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Writes a converted skybox texture to `cache_path`, so that later runs can load it with
+/// [`try_load_cached`] instead of converting the source image again. `key` identifies the source
+/// and conversion; see [`skybox_cache_key`].
+///
+/// The file is a small header followed by the raw texture data, which loads much faster than
+/// decoding and converting the source. The header records the texture's size, format (including
+/// whether it's sRGB), mip levels, and sampler, so the loaded image is the same as the one cached,
+/// except for the sampler's compare function, border color and label. Only common
+/// uncompressed and BC formats are supported; other formats return
+/// [`SkyboxError::UnsupportedFormat`]. Returns [`SkyboxError::WriteFailed`] if writing the file
+/// fails.
+///
+/// A typical setup loads from the cache if it can, and otherwise converts the source and caches the
+/// result once [`SkyboxConversionEvent::Converted`] is sent:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use std::path::Path;
+/// # use bevy_skybox_cubemap::*;
+/// const SOURCE: &str = "assets/sky.png";
+/// const CACHE: &str = "cache/sky.skybox";
+///
+/// fn load_sky(
+///     asset_server: Res<AssetServer>,
+///     mut images: ResMut<Assets<Image>>,
+///     mut conversion: ResMut<SkyboxTextureConversion>,
+/// ) -> Handle<Image> {
+///     let key = skybox_cache_key(Path::new(SOURCE), "stacked");
+///     if let Some(image) = key.and_then(|key| try_load_cached(Path::new(CACHE), key)) {
+///         return images.add(image);
+///     }
+///     let texture = asset_server.load("sky.png");
+///     conversion.make_array(texture.clone());
+///     texture
+/// }
+///
+/// fn cache_sky(mut events: EventReader<SkyboxConversionEvent>, images: Res<Assets<Image>>) {
+///     for event in events.iter() {
+///         if let SkyboxConversionEvent::Converted { handle, .. } = event {
+///             let key = skybox_cache_key(Path::new(SOURCE), "stacked");
+///             if let (Some(key), Some(image)) = (key, images.get(handle)) {
+///                 if let Err(err) = cache_converted(image, Path::new(CACHE), key) {
+///                     warn!("Couldn't cache the skybox: {}", err);
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn cache_converted(
+    image: &Image,
+    cache_path: &std::path::Path,
+    key: u64,
+) -> Result<(), SkyboxError> {
+    let descriptor = &image.texture_descriptor;
+    let format = CACHEABLE_FORMATS
+        .iter()
+        .position(|&format| format == descriptor.format)
+        .ok_or(SkyboxError::UnsupportedFormat(descriptor.format))?;
+    let size = descriptor.size;
+    let sampler = &image.sampler_descriptor;
+    fn index_of<T: PartialEq>(values: &[T], value: T) -> u8 {
+        values.iter().position(|other| *other == value).unwrap() as u8
+    }
+    let mut file = Vec::with_capacity(CACHE_HEADER_LEN + image.data.len());
+    file.extend_from_slice(CACHE_MAGIC);
+    file.extend_from_slice(&key.to_le_bytes());
+    for value in [
+        size.width,
+        size.height,
+        size.depth_or_array_layers,
+        format as u32,
+        descriptor.mip_level_count,
+    ] {
+        file.extend_from_slice(&value.to_le_bytes());
+    }
+    file.extend_from_slice(&[
+        index_of(&CACHEABLE_ADDRESS_MODES, sampler.address_mode_u),
+        index_of(&CACHEABLE_ADDRESS_MODES, sampler.address_mode_v),
+        index_of(&CACHEABLE_ADDRESS_MODES, sampler.address_mode_w),
+        index_of(&CACHEABLE_FILTER_MODES, sampler.mag_filter),
+        index_of(&CACHEABLE_FILTER_MODES, sampler.min_filter),
+        index_of(&CACHEABLE_FILTER_MODES, sampler.mipmap_filter),
+        sampler.anisotropy_clamp.map_or(0, |clamp| clamp.get()),
+    ]);
+    file.extend_from_slice(&sampler.lod_min_clamp.to_le_bytes());
+    file.extend_from_slice(&sampler.lod_max_clamp.to_le_bytes());
+    file.extend_from_slice(&(image.data.len() as u64).to_le_bytes());
+    file.extend_from_slice(&image.data);
+    let write_failed = |err: std::io::Error| SkyboxError::WriteFailed {
+        path: cache_path.to_path_buf(),
+        message: err.to_string(),
+    };
+    if let Some(dir) = cache_path.parent() {
+        std::fs::create_dir_all(dir).map_err(write_failed)?;
+    }
+    std::fs::write(cache_path, file).map_err(write_failed)
+}
+
+/// Loads a skybox texture written by [`cache_converted`] with the same `key`. The result is already
+/// a 6-layer array texture, so it can be added to `Assets<Image>` and used in a [`SkyboxMaterial`]
+/// without going through [`SkyboxTextureConversion`].
+///
+/// Returns `None` if there is no cache file, it was written for a different key (e.g. because the
+/// source image has changed), or it can't be read.
+pub fn try_load_cached(cache_path: &std::path::Path, key: u64) -> Option<Image> {
+    let file = std::fs::read(cache_path).ok()?;
+    let (header, data) = file.split_at(file.len().min(CACHE_HEADER_LEN));
+    let bytes_at = |offset: usize| -> [u8; 4] { header[offset..offset + 4].try_into().unwrap() };
+    let u32_at = |offset: usize| u32::from_le_bytes(bytes_at(offset));
+    if header.len() < CACHE_HEADER_LEN
+        || &header[..8] != CACHE_MAGIC
+        || u64::from_le_bytes(header[8..16].try_into().unwrap()) != key
+    {
+        return None;
+    }
+    let size = Extent3d {
+        width: u32_at(16),
+        height: u32_at(20),
+        depth_or_array_layers: u32_at(24),
+    };
+    let format = *CACHEABLE_FORMATS.get(u32_at(28) as usize)?;
+    let mip_level_count = u32_at(32);
+    let address_mode = |offset: usize| CACHEABLE_ADDRESS_MODES.get(header[offset] as usize);
+    let filter_mode = |offset: usize| CACHEABLE_FILTER_MODES.get(header[offset] as usize);
+    let sampler_descriptor = SamplerDescriptor {
+        address_mode_u: *address_mode(36)?,
+        address_mode_v: *address_mode(37)?,
+        address_mode_w: *address_mode(38)?,
+        mag_filter: *filter_mode(39)?,
+        min_filter: *filter_mode(40)?,
+        mipmap_filter: *filter_mode(41)?,
+        anisotropy_clamp: std::num::NonZeroU8::new(header[42]),
+        lod_min_clamp: f32::from_le_bytes(bytes_at(43)),
+        lod_max_clamp: f32::from_le_bytes(bytes_at(47)),
+        ..Default::default()
+    };
+    let len = u64::from_le_bytes(header[51..59].try_into().unwrap());
+    if size.width != size.height || !(1..=32).contains(&mip_level_count) {
+        return None;
+    }
+    let expected = layer_byte_len(size.width, mip_level_count, format) as u64
+        * size.depth_or_array_layers as u64;
+    if len != data.len() as u64 || len != expected {
+        return None;
+    }
+    // `Image::new` only accepts a single uncompressed mip level, so fill in the image directly.
+    let mut image = Image {
+        data: data.to_vec(),
+        sampler_descriptor,
+        ..Default::default()
+    };
+    let descriptor = &mut image.texture_descriptor;
+    descriptor.size = size;
+    descriptor.format = format;
+    descriptor.dimension = TextureDimension::D2;
+    descriptor.mip_level_count = mip_level_count;
+    Some(image)
+}
+
+/// Gets the number of bytes in a single layer of a texture with `face_size` by `face_size` faces and
+/// `mip_level_count` mip levels, which are stored one after the other in each layer.
+fn layer_byte_len(face_size: u32, mip_level_count: u32, format: TextureFormat) -> usize {
+    (0..mip_level_count.max(1))
+        .map(|level| face_byte_len((face_size >> level).max(1), format))
+        .sum()
+}
+
+/// Gets the number of bytes in a single `face_size` by `face_size` face in the given format. Works
+/// for block-compressed formats too.
+fn face_byte_len(face_size: u32, format: TextureFormat) -> usize {
+    let info = format.describe();
+    let (block_width, block_height) = info.block_dimensions;
+    let blocks_wide = (face_size as usize + block_width as usize - 1) / block_width as usize;
+    let blocks_high = (face_size as usize + block_height as usize - 1) / block_height as usize;
+    blocks_wide * blocks_high * info.block_size as usize
+}
+
+/// Errors which can occur in the skybox APIs which return a `Result`, and while converting textures
+/// queued in [`SkyboxTextureConversion`], where they are logged along with the texture's handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkyboxError {
+    /// One of the faces passed in had the wrong number of bytes for its size and format.
+    FaceSizeMismatch {
+        /// Layer index of the face which had the wrong size.
+        face: usize,
+        /// Number of bytes expected for the face.
+        expected: usize,
+        /// Number of bytes actually in the face.
+        actual: usize,
+    },
+    /// A vertically stacked image's height can't be evenly split into 6 faces.
+    NotDivisibleBySix {
+        /// Height of the image.
+        height: u32,
+    },
+    /// The conversion needs to work with individual pixels, which isn't possible for this
+    /// (block-compressed) format.
+    UnsupportedFormat(TextureFormat),
+    /// The faces of the skybox aren't square.
+    FaceNotSquare {
+        /// Width of each face.
+        width: u32,
+        /// Height of each face.
+        height: u32,
+    },
+    /// A strip of faces doesn't have the aspect ratio its layout needs, e.g. a vertically stacked
+    /// image whose height isn't exactly 6 times its width, so its faces wouldn't be square.
+    WrongAspectRatio {
+        /// Width and height of the image.
+        image_size: (u32, u32),
+        /// Width to height ratio the image needs, e.g. `(1, 6)` for a single frame stacked
+        /// vertically.
+        expected: (u32, u32),
+    },
+    /// A vertically stacked image's height can't be evenly split into the requested number of
+    /// layers.
+    NotDivisibleIntoLayers {
+        /// Height of the image.
+        height: u32,
+        /// Number of layers the image was supposed to be split into.
+        layers: u32,
+    },
+    /// The faces of a block-compressed image don't line up with the format's blocks, so the image
+    /// can't be split into faces without splitting blocks.
+    FaceNotBlockAligned {
+        /// Width and height of each face.
+        face_size: (u32, u32),
+        /// Width and height of the format's blocks.
+        block_size: (u32, u32),
+    },
+    /// A skybox texture failed to load, so it can't be converted.
+    TextureLoadFailed {
+        /// Path the texture was loaded from, if it was loaded from a file.
+        path: Option<String>,
+    },
+    /// A skybox texture isn't loaded (or the material has no texture).
+    TextureNotLoaded,
+    /// Writing a file failed.
+    WriteFailed {
+        /// The file which couldn't be written.
+        path: std::path::PathBuf,
+        /// Description of the underlying error.
+        message: String,
+    },
+    /// The faces of an atlas or a [`SkyboxAssembler`] aren't all the same size.
+    AtlasFaceSizeMismatch {
+        /// Index of the first face with a different size, in layer order.
+        face: usize,
+        /// Size of the first face.
+        expected: (u32, u32),
+        /// Size of the mismatched face.
+        actual: (u32, u32),
+    },
+    /// A face of an atlas, or a [`SkyboxAssembler::face_region`], is empty or extends past the
+    /// edges of the image.
+    AtlasFaceOutOfBounds {
+        /// Index of the face, in layer order.
+        face: usize,
+        /// Width and height of the atlas image.
+        image_size: (u32, u32),
+    },
+    /// A grid layout doesn't have room for 6 faces, or a face index is outside of the grid.
+    InvalidGrid {
+        /// Number of columns in the grid.
+        cols: u32,
+        /// Number of rows in the grid.
+        rows: u32,
+        /// The face index which is out of range, if that's the problem.
+        index: Option<u32>,
+    },
+    /// The converted texture would have more array layers than the device supports, e.g. because an
+    /// animated skybox has too many frames.
+    TooManyLayers {
+        /// Number of layers the texture would have.
+        layers: u32,
+        /// Largest number of layers the device supports.
+        max: u32,
+    },
+    /// No image was given for a face of a [`SkyboxAssembler`].
+    MissingFace(SkyboxFace),
+    /// The images of the faces of a [`SkyboxAssembler`] don't all have the same format.
+    FaceFormatMismatch {
+        /// Layer index of the first face with a different format.
+        face: usize,
+        /// Format of the first face.
+        expected: TextureFormat,
+        /// Format of the mismatched face.
+        actual: TextureFormat,
+    },
+    /// An image's size can't be evenly split into the cells of a grid.
+    NotDivisibleIntoGrid {
+        /// Width and height of the image.
+        image_size: (u32, u32),
+        /// Number of columns in the grid.
+        cols: u32,
+        /// Number of rows in the grid.
+        rows: u32,
+    },
+    /// A texture can't be changed to the format declared with
+    /// [`SkyboxTextureConversion::expect_format`].
+    FormatNotConvertible {
+        /// Format of the texture.
+        from: TextureFormat,
+        /// Format the texture was expected to have.
+        to: TextureFormat,
+    },
+    /// A texture which is already an array texture doesn't have the number of layers the skybox
+    /// needs.
+    WrongLayerCount {
+        /// Number of layers of the texture.
+        layers: u32,
+        /// Number of layers the skybox needs: 6 for each frame.
+        expected: u32,
+    },
+    /// A texture passed to [`SkyboxGpuConversion::make_array`] can't be copied from on the GPU,
+    /// because it wasn't created with [`TextureUsages::COPY_SRC`].
+    NotCopySource,
+    /// A [`SkyboxMaterialDef`] was written by a newer version of this crate than the one reading it.
+    #[cfg(feature = "serde")]
+    UnsupportedDefVersion {
+        /// Version of the definition.
+        version: u32,
+        /// Newest version this crate can read.
+        supported: u32,
+    },
+}
+
+impl std::fmt::Display for SkyboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkyboxError::FaceSizeMismatch {
+                face,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "skybox face {} has {} bytes, but {} bytes were expected",
+                face, actual, expected
+            ),
+            SkyboxError::NotDivisibleBySix { height } => write!(
+                f,
+                "stacked skybox image has height {}, which is not divisible by 6",
+                height
+            ),
+            SkyboxError::UnsupportedFormat(format) => {
+                write!(f, "skybox conversion does not support format {:?}", format)
+            }
+            SkyboxError::FaceNotSquare { width, height } => write!(
+                f,
+                "skybox faces are {}x{}, but they must be square",
+                width, height
+            ),
+            SkyboxError::WrongAspectRatio {
+                image_size,
+                expected,
+            } => write!(
+                f,
+                "skybox image is {}x{}, but it must have an aspect ratio of {}:{}",
+                image_size.0, image_size.1, expected.0, expected.1
+            ),
+            SkyboxError::NotDivisibleIntoLayers { height, layers } => write!(
+                f,
+                "stacked skybox image has height {}, which is not divisible into {} layers",
+                height, layers
+            ),
+            SkyboxError::FaceNotBlockAligned {
+                face_size,
+                block_size,
+            } => write!(
+                f,
+                "skybox faces are {}x{}, which is not a multiple of the {}x{} compression blocks",
+                face_size.0, face_size.1, block_size.0, block_size.1
+            ),
+            SkyboxError::TextureLoadFailed { path } => match path {
+                Some(path) => write!(f, "skybox texture {:?} failed to load", path),
+                None => write!(f, "skybox texture failed to load"),
+            },
+            SkyboxError::TextureNotLoaded => write!(f, "skybox texture is not loaded"),
+            SkyboxError::WriteFailed { path, message } => {
+                write!(f, "failed to write {:?}: {}", path, message)
+            }
+            SkyboxError::AtlasFaceSizeMismatch {
+                face,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "atlas face {} is {}x{}, but face 0 is {}x{}",
+                face, actual.0, actual.1, expected.0, expected.1
+            ),
+            SkyboxError::AtlasFaceOutOfBounds { face, image_size } => write!(
+                f,
+                "atlas face {} is empty or outside of the {}x{} image",
+                face, image_size.0, image_size.1
+            ),
+            SkyboxError::InvalidGrid { cols, rows, index } => match index {
+                Some(index) => write!(
+                    f,
+                    "face index {} is outside of the {}x{} grid",
+                    index, cols, rows
+                ),
+                None => write!(f, "a {}x{} grid can't hold 6 faces", cols, rows),
+            },
+            SkyboxError::NotDivisibleIntoGrid {
+                image_size,
+                cols,
+                rows,
+            } => write!(
+                f,
+                "image is {}x{}, which can't be split into a {}x{} grid",
+                image_size.0, image_size.1, cols, rows
+            ),
+            SkyboxError::MissingFace(face) => write!(f, "no image was given for face {:?}", face),
+            SkyboxError::FaceFormatMismatch {
+                face,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "skybox face {} has format {:?}, but face 0 has format {:?}",
+                face, actual, expected
+            ),
+            SkyboxError::TooManyLayers { layers, max } => write!(
+                f,
+                "skybox texture would have {} layers, but the device supports at most {}",
+                layers, max
+            ),
+            SkyboxError::FormatNotConvertible { from, to } => write!(
+                f,
+                "skybox texture has format {:?}, which can't be changed to {:?}",
+                from, to
+            ),
+            SkyboxError::WrongLayerCount { layers, expected } => write!(
+                f,
+                "skybox array texture has {} layers, but {} are needed",
+                layers, expected
+            ),
+            SkyboxError::NotCopySource => write!(
+                f,
+                "skybox texture can't be copied on the GPU without the COPY_SRC usage"
+            ),
+            #[cfg(feature = "serde")]
+            SkyboxError::UnsupportedDefVersion { version, supported } => write!(
+                f,
+                "skybox material definition has version {}, but only versions up to {} are \
+                 supported",
+                version, supported
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SkyboxError {}
+
+impl From<conversion::ConversionError> for SkyboxError {
+    fn from(error: conversion::ConversionError) -> Self {
+        match error {
+            conversion::ConversionError::FaceSizeMismatch {
+                face,
+                expected,
+                actual,
+            } => SkyboxError::FaceSizeMismatch {
+                face,
+                expected,
+                actual,
+            },
+        }
     }
 }
 
-/// Handle to use to reference the skybox pipeline.
-const SKYBOX_VERTEX_SHADER_HANDLE: HandleUntyped =
+/// The previous name of [`SkyboxError`], from when it was only used for conversions.
+#[deprecated(note = "renamed to `SkyboxError`")]
+pub type SkyboxConversionError = SkyboxError;
+
+/// Handle of the skybox vertex shader. Overwriting the shader at this handle after adding the
+/// [`SkyboxPlugin`] replaces it for every skybox.
+pub const SKYBOX_VERTEX_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 16037920303847147810);
-const SKYBOX_FRAGMENT_SHADER_HANDLE: HandleUntyped =
+/// Handle of the skybox fragment shader. Overwriting the shader at this handle after adding the
+/// [`SkyboxPlugin`] replaces it for every skybox, as long as the replacement uses the same
+/// bindings:
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy::render::render_resource::{Shader, ShaderStage};
+/// # use bevy_skybox_cubemap::{SkyboxPlugin, SKYBOX_FRAGMENT_SHADER_HANDLE};
+/// # const MY_SKYBOX_FRAG: &str = "";
+/// let mut app = App::new();
+/// app.add_plugins(DefaultPlugins)
+///     .add_plugin(SkyboxPlugin::default());
+/// let mut shaders = app.world.get_resource_mut::<Assets<Shader>>().unwrap();
+/// shaders.set_untracked(
+///     SKYBOX_FRAGMENT_SHADER_HANDLE,
+///     Shader::from_glsl(MY_SKYBOX_FRAG, ShaderStage::Fragment),
+/// );
+/// ```
+pub const SKYBOX_FRAGMENT_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7123103161671906218);
+/// Handle of the fragment shader used while [`SkyboxDebugFaces`] is enabled.
+pub const SKYBOX_DEBUG_FRAGMENT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1548313275632160541);
+/// Handle of the vertex shader which draws skyboxes as a single triangle covering the view. See
+/// [`SkyboxPlugin::fullscreen_triangle`].
+pub const SKYBOX_FULLSCREEN_VERTEX_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 13680437416046273305);
+/// Handle of the fragment shader which stretches skyboxes drawn at half resolution over the view.
+/// See [`SkyboxPlugin::half_resolution`].
+pub const SKYBOX_UPSAMPLE_FRAGMENT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 5097316843072269731);
+
+/// Handle to the fully transparent 6 layer array texture used when a material has no overlay. The
+/// [`SkyboxPlugin`] adds this texture.
+pub const SKYBOX_EMPTY_TEXTURE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 2923174526458823312);
+
+/// Handle to the white 6 layer array texture used when a material has no texture, so color-only
+/// materials can be drawn right away. The [`SkyboxPlugin`] adds this texture.
+pub const SKYBOX_WHITE_TEXTURE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 6310581796623094517);
+
+/// Handle to the 1x1x1 3d texture used when a material has no color grading LUT. The
+/// [`SkyboxPlugin`] adds this texture.
+pub const SKYBOX_EMPTY_LUT_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 11790231546338264217);
+
+/// Handle to the white 1x1 texture used when a material has no mask texture. The shader skips the
+/// mask for those materials. The [`SkyboxPlugin`] adds this texture.
+pub const SKYBOX_EMPTY_MASK_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 12205894556362745046);
 
-/// Handle to use to reference the skybox mesh.
-const SKYBOX_MESH_HANDLE: HandleUntyped =
+/// Handle of the cube mesh used by [`SkyboxBundle`]. The vertex shader makes the size of the mesh
+/// irrelevant, but overwriting the mesh at this handle after adding the [`SkyboxPlugin`] can be
+/// used to replace it with a finer one for every skybox.
+pub const SKYBOX_MESH_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 7423141153313829192);
@@ -17,19 +17,96 @@
 #define SKYBOXMATERIAL_TEXTURE
 
 layout(location = 0) in vec3 TexCoords;
+// Clip space position of the fragment, for the mask's screen-space coordinates.
+layout(location = 2) in vec4 ClipPosition;
 
 layout(set = 1, binding = 0) uniform CustomMaterial {
   vec4 color;
+  // Per-face tint, indexed by layer.
+  vec4 face_tints[6];
+  uint flags;
+  // Frame of an animated skybox to show. Each frame is 6 layers.
+  uint frame;
+  // Inverse of the material's rotation, applied to the view direction.
+  mat3 rotation;
+  // Amount to darken the sky below the horizon, from 0 to 1.
+  float horizon_darkening;
+  // Settings of the procedural sky. sun_direction is normalized, and turbidity is at least 1.
+  float turbidity;
+  vec4 sun_direction;
+  // How far through the fade from the texture to the low resolution texture this material is.
+  float lod_blend;
+  // Per-face brightness, indexed by layer as face_brightness[layer / 4][layer % 4].
+  vec4 face_brightness[2];
+  // How far the texture has faded in, from 0 (only the color) to 1.
+  float fade;
+  // Color drawn below the horizon when SKYBOX_FLAGS_GROUND is set.
+  vec4 ground_color;
+  // Fraction of each face cropped off at each edge, from 0 to just under 0.5.
+  float face_inset;
+  // Seconds since startup, for SKYBOX_FLAGS_TWINKLE.
+  float time;
+  // Bias added to the mip level of texture samples.
+  float lod_bias;
+  // Color of the medium the sky is seen through, and how much of the sky it replaces, from 0 to 1.
+  vec4 environment_tint;
+  float environment_density;
+  // Color of the sun disc drawn when SKYBOX_FLAGS_SUN_DISC is set, and its angular radius in
+  // radians. The disc is centered on sun_direction.
+  vec4 sun_color;
+  float sun_angular_radius;
+  // How much the brightness of color varies with the view direction, from 0 to 1.
+  float color_variation;
+  // Extra factor the alpha of the skybox is multiplied by, on top of the alpha of color and the
+  // tints.
+  float alpha_scale;
+  // Contrast around 0.5 and power curve applied to the texture's linear color. 1 has no effect.
+  float contrast;
+  float gamma;
+  // Color of the debug grid drawn when SKYBOX_FLAGS_GRID is set.
+  vec4 grid_color;
+  // Tints of the upper and lower hemispheres, blended over horizon_blend above and below the
+  // horizon.
+  vec4 sky_tint;
+  vec4 ground_tint;
+  float horizon_blend;
 };
 
+// These must match the SKYBOX_FLAGS_* constants in lib.rs.
+const uint SKYBOX_FLAGS_DITHER = 1u << 0;
+const uint SKYBOX_FLAGS_LUT = 1u << 1;
+const uint SKYBOX_FLAGS_PROCEDURAL = 1u << 2;
+const uint SKYBOX_FLAGS_OUTPUT_SRGB = 1u << 3;
+const uint SKYBOX_FLAGS_NO_SEAM_BLEND = 1u << 4;
+const uint SKYBOX_FLAGS_MIRROR = 1u << 5;
+const uint SKYBOX_FLAGS_GROUND = 1u << 6;
+const uint SKYBOX_FLAGS_TWINKLE = 1u << 7;
+const uint SKYBOX_FLAGS_SUN_DISC = 1u << 8;
+const uint SKYBOX_FLAGS_GAMMA_COLOR = 1u << 9;
+const uint SKYBOX_FLAGS_MASK = 1u << 10;
+const uint SKYBOX_FLAGS_GRID = 1u << 11;
+
 // layout(set = 2, binding = 0) uniform SkyboxMaterial_color {
 //     vec4 color;
 // };
 
 // #ifdef SKYBOXMATERIAL_TEXTURE
+#ifdef SKYBOX_CUBE
+// The faces of the current frame, with the Z faces swapped. See SkyboxMaterial::cube_sampling.
+layout(set = 1, binding = 1) uniform textureCube SkyboxMaterial_texture;
+#else
 layout(set = 1, binding = 1) uniform texture2DArray SkyboxMaterial_texture;
+#endif
 layout(set = 1, binding = 2) uniform sampler SkyboxMaterial_texture_sampler;
 // #endif
+layout(set = 1, binding = 3) uniform texture2DArray SkyboxMaterial_overlay_texture;
+layout(set = 1, binding = 4) uniform sampler SkyboxMaterial_overlay_texture_sampler;
+layout(set = 1, binding = 5) uniform texture3D SkyboxMaterial_lut;
+layout(set = 1, binding = 6) uniform sampler SkyboxMaterial_lut_sampler;
+// Sampled with SkyboxMaterial_texture_sampler.
+layout(set = 1, binding = 7) uniform texture2DArray SkyboxMaterial_low_res_texture;
+layout(set = 1, binding = 8) uniform texture2D SkyboxMaterial_mask_texture;
+layout(set = 1, binding = 9) uniform sampler SkyboxMaterial_mask_texture_sampler;
 
 layout(location = 0) out vec4 o_Target;
 
@@ -38,6 +115,7 @@ layout(location = 0) out vec4 o_Target;
 // So instead we just write our own cubemap sampler and use an array texture, which does work
 // correctly.
 // TODO: Just use a normal cubemap once those work in bevy.
+// Keep in sync with skybox_face_for_direction in lib.rs.
 vec3 sampleCubeHacky(const vec3 ray) {
     vec3 rayAbs = abs(ray);
     float maxAdjust;
@@ -59,13 +137,304 @@ vec3 sampleCubeHacky(const vec3 ray) {
     return vec3(uv * maxAdjust + 0.5, faceIndex);
 }
 
+// Scales face UVs toward the center of the face to crop face_inset off each edge.
+vec2 insetUv(const vec2 uv) {
+    return 0.5 + (uv - 0.5) * (1.0 - 2.0 * face_inset);
+}
+
+// Samples the texture, or the low resolution texture if lowRes is set.
+vec4 sampleTexture(const bool lowRes, const vec3 uvLayer) {
+#ifdef SKYBOX_CUBE
+    // The main texture isn't an array texture in this variant, and is sampled in main instead.
+    if (true) {
+#else
+    if (lowRes) {
+#endif
+        return texture(
+            sampler2DArray(SkyboxMaterial_low_res_texture, SkyboxMaterial_texture_sampler),
+            uvLayer,
+            lod_bias
+        );
+    }
+    return texture(
+        sampler2DArray(SkyboxMaterial_texture, SkyboxMaterial_texture_sampler),
+        uvLayer,
+        lod_bias
+    );
+}
+
+// Gets the size of the faces of the texture, or of the low resolution texture if lowRes is set.
+vec2 textureFaceSize(const bool lowRes) {
+#ifdef SKYBOX_CUBE
+    if (true) {
+#else
+    if (lowRes) {
+#endif
+        return vec2(textureSize(
+            sampler2DArray(SkyboxMaterial_low_res_texture, SkyboxMaterial_texture_sampler), 0).xy);
+    }
+    return vec2(textureSize(
+        sampler2DArray(SkyboxMaterial_texture, SkyboxMaterial_texture_sampler), 0).xy);
+}
+
+// Samples the skybox texture in the given direction, blending across the seams between faces.
+//
+// Since the faces are separate layers of an array texture, bilinear filtering can't blend texels of
+// one face with those of the face next to it, which leaves a visible line along each edge of the
+// cube. To hide it, samples within half a texel of an edge are clamped to the face's outermost
+// texel centers and mixed with the closest texel center on the neighboring face, weighted by how
+// far past the last texel center the sample is. Right on the edge both faces are weighted equally,
+// so the two sides of the seam meet at the same color. The neighboring face is sampled even where
+// its weight is 0, since branching on the weight would leave the implicit derivatives of the
+// samples undefined.
+vec4 sampleSkyboxSeamless(const vec3 ray, const float layerOffset, const bool lowRes) {
+    vec3 uvIndex = sampleCubeHacky(ray);
+    uvIndex.xy = insetUv(uvIndex.xy);
+    // Materials with a custom address mode let the sampler handle the edges instead.
+    if ((flags & SKYBOX_FLAGS_NO_SEAM_BLEND) != 0u) {
+        return sampleTexture(lowRes, vec3(uvIndex.xy, uvIndex.z + layerOffset));
+    }
+    vec2 size = textureFaceSize(lowRes);
+    vec2 halfTexel = 0.5 / size;
+    vec2 clamped = clamp(uvIndex.xy, halfTexel, 1.0 - halfTexel);
+    vec4 result = sampleTexture(lowRes, vec3(clamped, uvIndex.z + layerOffset));
+
+    // How far past the outermost texel centers the sample is, in texels (at most 0.5).
+    vec2 over = abs(uvIndex.xy - clamped) * size;
+    float weight = max(over.x, over.y);
+
+    // Project onto the surface of a cube with faces at +/-1, then pull the point in along this
+    // face's axis to the depth of the neighboring face's outermost texel centers. That makes the
+    // coordinate close to the edge the largest one, so it selects the neighboring face.
+    vec3 rayAbs = abs(ray);
+    vec3 point = ray / max(max(rayAbs.x, rayAbs.y), rayAbs.z);
+    int axis = int(uvIndex.z + 0.5) / 2;
+    point[axis] = sign(point[axis]) * (1.0 - 1.0 / size.x);
+    vec3 neighborUvIndex = sampleCubeHacky(point);
+    neighborUvIndex.xy = insetUv(neighborUvIndex.xy);
+    vec4 neighbor = sampleTexture(
+        lowRes,
+        vec3(clamp(neighborUvIndex.xy, halfTexel, 1.0 - halfTexel),
+             neighborUvIndex.z + layerOffset)
+    );
+    return mix(result, neighbor, weight);
+}
+
+// Encodes a linear color with the sRGB transfer function.
+vec3 linearToSrgb(const vec3 linear) {
+    vec3 clamped = clamp(linear, 0.0, 1.0);
+    vec3 low = clamped * 12.92;
+    vec3 high = 1.055 * pow(clamped, vec3(1.0 / 2.4)) - 0.055;
+    return mix(high, low, lessThanEqual(clamped, vec3(0.0031308)));
+}
+
+// Decodes a color encoded with the sRGB transfer function.
+vec3 srgbToLinear(const vec3 srgb) {
+    vec3 clamped = clamp(srgb, 0.0, 1.0);
+    vec3 low = clamped / 12.92;
+    vec3 high = pow((clamped + 0.055) / 1.055, vec3(2.4));
+    return mix(high, low, lessThanEqual(clamped, vec3(0.04045)));
+}
+
+// Screen-space noise of about one 8-bit step, used to break up banding. This is interleaved
+// gradient noise, which looks less patterned than an ordered dither.
+float dither(const vec2 fragCoord) {
+    float noise = fract(52.9829189 * fract(dot(fragCoord, vec2(0.06711056, 0.00583715))));
+    return (noise - 0.5) / 255.0;
+}
+
+// A cheap analytic daytime sky, used in place of the texture when SKYBOX_FLAGS_PROCEDURAL is set.
+// It loosely follows the shape of the Preetham model: a gradient from the horizon to the zenith,
+// a glow around the sun which widens with turbidity, and warmer colors as the sun gets low.
+vec3 proceduralSky(const vec3 ray) {
+    vec3 dir = normalize(ray);
+    vec3 sun = sun_direction.xyz;
+    float haze = clamp((turbidity - 1.0) / 9.0, 0.0, 1.0);
+
+    vec3 zenith = mix(vec3(0.10, 0.30, 0.75), vec3(0.45, 0.55, 0.70), haze);
+    vec3 horizon = mix(vec3(0.65, 0.78, 0.95), vec3(0.85, 0.85, 0.82), haze);
+    // Toward sunset the horizon turns orange and the whole sky dims.
+    float sunHeight = clamp(sun.y, -0.2, 1.0);
+    float sunset = 1.0 - smoothstep(0.0, 0.35, sunHeight);
+    horizon = mix(horizon, vec3(0.95, 0.55, 0.30), sunset);
+    float daylight = smoothstep(-0.2, 0.15, sunHeight);
+
+    vec3 sky = mix(horizon, zenith, sqrt(max(dir.y, 0.0)));
+    // Below the horizon, fade to a dim ground color.
+    sky = mix(sky, horizon * 0.3, 1.0 - smoothstep(-0.2, 0.0, dir.y));
+
+    float cosGamma = max(dot(dir, sun), 0.0);
+    vec3 glow = vec3(1.0, 0.85, 0.65) * pow(cosGamma, mix(48.0, 6.0, haze)) * (0.4 + 0.4 * haze);
+    vec3 disk = vec3(20.0) * smoothstep(0.9995, 0.9998, cosGamma);
+    return (sky + glow) * daylight + disk * step(0.0, dir.y);
+}
+
+// Brightness of the overlay in the given direction, varying over time. Directions are snapped to a
+// grid so each star twinkles as a whole, with its own phase and speed from a hash of its cell.
+float twinkle(const vec3 ray) {
+    vec3 cell = floor(normalize(ray) * 512.0);
+    float hash = fract(sin(dot(cell, vec3(12.9898, 78.233, 37.719))) * 43758.5453);
+    float speed = 1.5 + 2.5 * fract(hash * 7.31);
+    return 0.7 + 0.3 * sin(time * speed + hash * 6.2831853);
+}
+
+// Coverage of the sun disc in the given direction, from 0 outside of it to 1 inside, with a soft
+// edge.
+float sunDisc(const vec3 ray) {
+    vec3 dir = normalize(ray);
+    // atan of the cross and dot products stays precise for the tiny angles of a small sun, unlike
+    // acos of the dot product.
+    float angle = atan(length(cross(dir, sun_direction.xyz)), dot(dir, sun_direction.xyz));
+    float edge = max(sun_angular_radius * 0.15, 1.0e-4);
+    return 1.0 - smoothstep(sun_angular_radius - edge, sun_angular_radius + edge, angle);
+}
+
+// Coverage of the debug grid in the given direction, from 0 off the lines to 1 on them: latitude
+// and longitude lines every 15 degrees, and thicker lines along the edges of the cube's faces.
+// Line widths are measured in pixels with fwidth. The derivatives jump where longitude wraps around
+// and between faces, so they're capped to keep the lines thin there.
+float debugGrid(const vec3 ray) {
+    vec3 dir = normalize(ray);
+    const float linesPerRadian = 12.0 / 3.14159265;
+    vec2 angles = vec2(atan(dir.x, -dir.z), asin(clamp(dir.y, -1.0, 1.0))) * linesPerRadian;
+    vec2 angleWidth = min(fwidth(angles), vec2(0.05));
+    vec2 toLine = abs(fract(angles + 0.5) - 0.5);
+    vec2 lines = 1.0 - smoothstep(angleWidth * 0.5, angleWidth * 1.5, toLine);
+
+    vec2 uv = sampleCubeHacky(ray).xy;
+    float uvWidth = min(max(fwidth(uv.x), fwidth(uv.y)), 0.01);
+    vec2 toEdge = min(uv, 1.0 - uv);
+    float edges = 1.0 - smoothstep(uvWidth, uvWidth * 2.5, min(toEdge.x, toEdge.y));
+    return max(max(lines.x, lines.y), edges);
+}
+
+#ifdef SKYBOX_MISSING_PATTERN
+// Pattern drawn in place of a missing texture: a low contrast checkerboard, darkening towards the
+// bottom of the sky so it's still possible to tell which way is up.
+vec4 missingPattern(const vec3 ray) {
+    vec3 uvIndex = sampleCubeHacky(ray);
+    ivec2 cell = ivec2(floor(uvIndex.xy * 8.0));
+    float check = ((cell.x + cell.y) & 1) == 0 ? 0.35 : 0.45;
+    float gradient = 0.75 + 0.25 * normalize(ray).y;
+    return vec4(vec3(check * gradient), 1.0);
+}
+#endif
+
 void main() {
+    // Masked out fragments are only discarded at the end. Discarding here would leave the rest of
+    // the shader in non-uniform control flow, where texture sampling and fwidth are undefined.
+    bool masked = false;
+    if ((flags & SKYBOX_FLAGS_MASK) != 0u) {
+        // Clip space y points up, while texture coordinates point down.
+        vec2 screenUv = ClipPosition.xy / ClipPosition.w * vec2(0.5, -0.5) + 0.5;
+        float mask = texture(
+            sampler2D(SkyboxMaterial_mask_texture, SkyboxMaterial_mask_texture_sampler),
+            screenUv
+        ).r;
+        masked = mask < 0.5;
+    }
+    vec3 direction = TexCoords;
+    if ((flags & SKYBOX_FLAGS_MIRROR) != 0u) {
+        // Reflect the lower half of the sky across the horizon plane.
+        direction.y = abs(direction.y);
+    }
+    vec3 ray = rotation * direction;
+#ifdef SKYBOX_MISSING_PATTERN
+    o_Target = missingPattern(ray);
+    if (masked) {
+        discard;
+    }
+    return;
+#endif
 // #ifdef SKYBOXMATERIAL_TEXTURE
-    vec3 uvIndex = sampleCubeHacky(TexCoords);
-    o_Target = texture(
-        sampler2DArray(SkyboxMaterial_texture, SkyboxMaterial_texture_sampler),
-        uvIndex
-    ) * color;
+    vec3 uvIndex = sampleCubeHacky(ray);
+    int face = int(uvIndex.z + 0.5);
+    vec4 base;
+    if ((flags & SKYBOX_FLAGS_PROCEDURAL) != 0u) {
+        base = vec4(proceduralSky(ray), 1.0);
+    } else {
+#ifdef SKYBOX_CUBE
+        // The GPU's cubemap convention is mirrored along Z relative to ours, which the swapped Z
+        // faces and this flip undo. The cube only holds the current frame.
+        base = texture(
+            samplerCube(SkyboxMaterial_texture, SkyboxMaterial_texture_sampler),
+            vec3(ray.xy, -ray.z)
+        );
+#else
+        base = sampleSkyboxSeamless(ray, 6.0 * float(frame), false);
+#endif
+        if (lod_blend > 0.0) {
+            base = mix(base, sampleSkyboxSeamless(ray, 6.0 * float(frame), true), lod_blend);
+        }
+    }
+    // The overlay is blended over the base using its own alpha. Materials without an overlay bind
+    // a fully transparent texture, so this leaves the base unchanged. The overlay isn't animated,
+    // so it always uses the first frame.
+    vec4 overlay = texture(
+        sampler2DArray(SkyboxMaterial_overlay_texture, SkyboxMaterial_overlay_texture_sampler),
+        vec3(uvIndex.xy, float(face))
+    );
+    if ((flags & SKYBOX_FLAGS_TWINKLE) != 0u) {
+        overlay.rgb *= twinkle(ray);
+    }
+    base.rgb = mix(base.rgb, overlay.rgb, overlay.a);
+    if (contrast != 1.0 || gamma != 1.0) {
+        base.rgb = pow(max((base.rgb - 0.5) * contrast + 0.5, 0.0), vec3(gamma));
+    }
+    base = mix(vec4(1.0), base, fade);
+    if ((flags & SKYBOX_FLAGS_GAMMA_COLOR) != 0u) {
+        // The color is in gamma space, so multiply the sky by it in gamma space too.
+        o_Target = vec4(srgbToLinear(linearToSrgb(base.rgb) * color.rgb), base.a * color.a);
+    } else {
+        o_Target = base * color;
+    }
+    o_Target.a *= alpha_scale;
+    if (color_variation > 0.0) {
+        // Lighter toward the top of the sky and slightly to one side. Both terms are smooth over
+        // the whole sphere, so there are no visible seams.
+        vec3 dir = normalize(ray);
+        o_Target.rgb *= 1.0 + color_variation * (0.35 * dir.y + 0.15 * dir.x);
+    }
+    o_Target *= face_tints[face];
+    if (sky_tint != vec4(1.0) || ground_tint != vec4(1.0)) {
+        // With no blend, the step at the horizon is still antialiased over about a pixel.
+        float height = normalize(TexCoords).y;
+        float softness = max(horizon_blend, fwidth(height));
+        float upper = smoothstep(-softness, softness, height);
+        o_Target.rgb *= mix(ground_tint.rgb, sky_tint.rgb, upper);
+    }
+    o_Target.rgb *= face_brightness[face / 4][face % 4];
+    float below = max(-normalize(TexCoords).y, 0.0);
+    o_Target.rgb *= 1.0 - horizon_darkening * below;
+    if ((flags & SKYBOX_FLAGS_SUN_DISC) != 0u) {
+        o_Target.rgb = mix(o_Target.rgb, sun_color.rgb, sunDisc(ray) * sun_color.a);
+    }
+    if ((flags & SKYBOX_FLAGS_GROUND) != 0u && TexCoords.y < 0.0) {
+        o_Target = ground_color;
+    }
+    if ((flags & SKYBOX_FLAGS_LUT) != 0u) {
+        // Map [0, 1] onto the centers of the first and last texels, so the ends of the range look
+        // up exactly the first and last entries of the table.
+        vec3 size = vec3(textureSize(
+            sampler3D(SkyboxMaterial_lut, SkyboxMaterial_lut_sampler), 0));
+        vec3 coords = clamp(o_Target.rgb, 0.0, 1.0) * (size - 1.0) / size + 0.5 / size;
+        o_Target.rgb = texture(
+            sampler3D(SkyboxMaterial_lut, SkyboxMaterial_lut_sampler), coords).rgb;
+    }
+    o_Target.rgb = mix(o_Target.rgb, environment_tint.rgb, environment_density);
+    if ((flags & SKYBOX_FLAGS_GRID) != 0u) {
+        o_Target.rgb = mix(o_Target.rgb, grid_color.rgb, debugGrid(ray) * grid_color.a);
+    }
+    if ((flags & SKYBOX_FLAGS_OUTPUT_SRGB) != 0u) {
+        o_Target.rgb = linearToSrgb(o_Target.rgb);
+    }
+    // Dither last, so the noise is about one step of whatever the target stores.
+    if ((flags & SKYBOX_FLAGS_DITHER) != 0u) {
+        o_Target.rgb += dither(gl_FragCoord.xy);
+    }
+    if (masked) {
+        discard;
+    }
 // #else
     // o_Target = color;
     // o_Target = vec4(depth, depth, depth, 1.0);
@@ -36,7 +36,17 @@ layout(set = 2, binding = 0) uniform Mesh {
 
 
 layout(location = 0) out vec3 TexCoords;
+
+// Depth (after the perspective divide) to draw the skybox at. This is just in front of the far
+// plane, which is at depth 0 with reverse-Z (Bevy's convention) and at depth 1 otherwise.
+#ifdef SKYBOX_STANDARD_DEPTH
+const float SKYBOX_DEPTH = 1.0 - 1.0e-6;
+#else
+const float SKYBOX_DEPTH = 1.0e-7;
+#endif
 layout(location = 1) out float depth;
+// Clip space position, which the fragment shader uses for screen-space effects.
+layout(location = 2) out vec4 ClipPosition;
 
 void main() {
   // ViewProj is Proj * inverse(View). We want to get Proj * inverse(untranslatedView). However,
@@ -56,13 +66,19 @@ void main() {
 
   // vec4 pos = untranslatedProj * untranslatedModel * vec4(Vertex_Position, 1.0);
 
-  // // We allow rotating the skybox, but not translating (since we need the position to match the
-  // // zeroed camera position). To do that, remove the translation from the model matrix.
-  vec4 pos = ViewProj * Model * vec4(Vertex_Position + WorldPosition, 1.0);
+  // We allow rotating the skybox, but not translating (since the cube has to stay centered on the
+  // camera). To do that, only the rotation and scale of the model matrix are applied.
+  vec4 pos = ViewProj * vec4(mat3(Model) * Vertex_Position + WorldPosition, 1.0);
 
-  // Use w as z to force the point as far back a possible for depth testing purposes. This makes
-  // sure it never draws in front of anything else.
-  gl_Position = vec4(pos.xy, 1.0 / (far + 10), pos.w);
+  // Force the point as far back as possible for depth testing purposes. This makes sure it never
+  // draws in front of anything else. The depth test passes for fragments at or in front of what's
+  // in the depth buffer, so the skybox shows wherever only the cleared far plane is, and any
+  // geometry nearer than the skybox covers it. We place it just in front of the far plane. Scaling
+  // by w makes the depth after the perspective divide exactly SKYBOX_DEPTH no matter how far the
+  // vertex is from the camera, so neither the size of the mesh nor the camera's near and far
+  // planes can cause the skybox to be clipped.
+  gl_Position = vec4(pos.xy, pos.w * SKYBOX_DEPTH, pos.w);
+  ClipPosition = gl_Position;
   // gl_Position = vec4(pos.xy, pos.w, pos.w);
   // depth = pos.w;
 
@@ -0,0 +1,72 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 450
+
+// Debug variant of skybox.frag which ignores the skybox texture and instead colors each face of the
+// cube by the axis it represents. Positive faces use a bright color and negative faces a dark one:
+// red for X, green for Y, blue for Z. Each face also gets one stripe per layer index (so +X has 1
+// stripe, -X has 2, ...) and a white square in the corner where the face's UV origin is, which makes
+// both the face order and the rotation of each face easy to check.
+
+layout(location = 0) in vec3 TexCoords;
+
+layout(location = 0) out vec4 o_Target;
+
+// Same face selection as sampleCubeHacky in skybox.frag. Keep these in sync.
+vec3 sampleCubeHacky(const vec3 ray) {
+    vec3 rayAbs = abs(ray);
+    float maxAdjust;
+    float faceIndex;
+    vec2 uv;
+    if (rayAbs.z >= rayAbs.x && rayAbs.z >= rayAbs.y) {
+        faceIndex = ray.z < 0.0 ? 5.0 : 4.0;
+        maxAdjust = 0.5 / rayAbs.z;
+        uv = vec2(ray.x * -sign(ray.z), -ray.y);
+    } else if (rayAbs.y >= rayAbs.x) {
+        faceIndex = ray.y < 0.0 ? 3.0 : 2.0;
+        maxAdjust = 0.5 / ray.y;
+        uv = vec2(ray.x * sign(ray.y), -ray.z);
+    } else {
+        faceIndex = ray.x < 0.0 ? 1.0 : 0.0;
+        maxAdjust = 0.5 / ray.x;
+        uv = vec2(ray.z, ray.y * -sign(ray.x));
+    }
+    return vec3(uv * maxAdjust + 0.5, faceIndex);
+}
+
+void main() {
+    vec3 uvIndex = sampleCubeHacky(TexCoords);
+    vec2 uv = uvIndex.xy;
+    int face = int(uvIndex.z + 0.5);
+
+    vec3 axisColor = vec3(0.0);
+    axisColor[face / 2] = 1.0;
+    // Negative faces are odd layers.
+    vec3 faceColor = (face % 2 == 0) ? axisColor : axisColor * 0.35;
+
+    // One stripe per layer index (1-based) across the middle of the face.
+    float stripes = float(face + 1);
+    float stripe = step(0.5, fract(uv.x * stripes * 2.0 + 0.25));
+    if (uv.y > 0.4 && uv.y < 0.6) {
+        faceColor = mix(faceColor, vec3(1.0) - faceColor, stripe);
+    }
+
+    // Mark the UV origin of the face to make rotations visible.
+    if (uv.x < 0.15 && uv.y < 0.15) {
+        faceColor = vec3(1.0);
+    }
+
+    o_Target = vec4(faceColor, 1.0);
+}
@@ -0,0 +1,57 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 450
+
+// Vertex shader for drawing the skybox as a single triangle covering the view, without a mesh. See
+// SkyboxPlugin::fullscreen_triangle.
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+  mat4 ViewProj;
+  mat4 View;
+  mat4 InverseView;
+  mat4 Projection;
+  vec3 WorldPosition;
+  float near;
+  float far;
+  float width;
+  float height;
+};
+
+layout(location = 0) out vec3 TexCoords;
+// Keep in sync with skybox.vert.
+layout(location = 2) out vec4 ClipPosition;
+
+// Keep in sync with skybox.vert.
+#ifdef SKYBOX_STANDARD_DEPTH
+const float SKYBOX_DEPTH = 1.0 - 1.0e-6;
+#else
+const float SKYBOX_DEPTH = 1.0e-7;
+#endif
+
+void main() {
+  // The vertices are (-1, -1), (3, -1) and (-1, 3) in clip space, which covers the whole view.
+  vec2 clip = vec2(
+    float((gl_VertexIndex & 1) << 2) - 1.0,
+    float((gl_VertexIndex & 2) << 1) - 1.0
+  );
+  gl_Position = vec4(clip, SKYBOX_DEPTH, 1.0);
+  ClipPosition = gl_Position;
+
+  // Unproject a point on the view ray through this vertex and rotate it into world space. Only the
+  // direction matters, so the depth of the point doesn't. The direction is linear in screen space,
+  // so interpolating it across the triangle gives the right ray for every fragment.
+  vec4 viewPosition = inverse(Projection) * vec4(clip, 0.5, 1.0);
+  TexCoords = mat3(View) * (viewPosition.xyz / viewPosition.w);
+}
@@ -0,0 +1,40 @@
+// Copyright 2021 Google LLC
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#version 450
+
+// Draws the skyboxes rendered at half resolution (see SkyboxPlugin::half_resolution) in place of
+// the skybox, stretching them over the view with bilinear filtering.
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+  mat4 ViewProj;
+  mat4 View;
+  mat4 InverseView;
+  mat4 Projection;
+  vec3 WorldPosition;
+  float near;
+  float far;
+  float width;
+  float height;
+};
+
+layout(set = 1, binding = 0) uniform texture2D SkyboxHalfRes_texture;
+layout(set = 1, binding = 1) uniform sampler SkyboxHalfRes_sampler;
+
+layout(location = 0) out vec4 o_Target;
+
+void main() {
+    vec2 uv = gl_FragCoord.xy / vec2(width, height);
+    o_Target = texture(sampler2D(SkyboxHalfRes_texture, SkyboxHalfRes_sampler), uv);
+}